@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::emit_error;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::Comma,
+    Attribute, Expr, Field, Ident, LitBool, Token, Type,
+};
+
+use crate::shape;
+
+/// Attributes, which configure how `#[network]` constructs a field.
+const CONFIG_ATTRS: [&str; 5] = ["init", "bias", "bias_init", "l2", "conv"];
+
+pub fn is_config_attr(attr: &Attribute) -> bool {
+    CONFIG_ATTRS.iter().any(|name| attr.path.is_ident(name))
+}
+
+/// Returns true if a field is configured with `#[conv(..)]`.
+pub fn has_conv(fields: &Punctuated<Field, Comma>) -> bool {
+    fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .any(|attr| attr.path.is_ident("conv"))
+}
+
+fn is_conv(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Conv2D"),
+        _ => false,
+    }
+}
+
+/// An argument of `#[conv(..)]`, e.g. `kernel = (3, 3)`.
+struct ConvArg {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for ConvArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(ConvArg { name, value })
+    }
+}
+
+fn parse_conv(attr: &Attribute) -> syn::Result<TokenStream> {
+    let args = attr.parse_args_with(Punctuated::<ConvArg, Token![,]>::parse_terminated)?;
+
+    let (mut input, mut kernel, mut blocks) = (None, None, None);
+
+    for arg in args {
+        let value = match arg.name.to_string().as_str() {
+            "input" => &mut input,
+            "kernel" => &mut kernel,
+            "blocks" => &mut blocks,
+            _ => {
+                return Err(syn::Error::new(
+                    arg.name.span(),
+                    "Unknown argument. Expected `input`, `kernel` or `blocks`.",
+                ))
+            }
+        };
+        *value = Some(arg.value);
+    }
+
+    match (input, kernel, blocks) {
+        (Some(input), Some(kernel), Some(blocks)) => Ok(quote!(#input, #kernel, #blocks)),
+        _ => Err(syn::Error::new(
+            attr.span(),
+            "expected `#[conv(input = (rows, cols), kernel = (rows, cols), blocks = n)]`",
+        )),
+    }
+}
+
+/// Returns the expression, which constructs the field in `WithDevice::with`.
+///
+/// `#[init(..)]`, `#[bias(..)]`, `#[bias_init(..)]` and `#[l2(..)]` set the corresponding options of the `LinearConfig` of a `Linear` field.
+/// `#[conv(input = .., kernel = .., blocks = ..)]` passes its arguments to `Conv2D::new`.
+/// Fields without these attributes are constructed with `WithDevice::with(device)`.
+pub fn construct_field(field: &Field) -> TokenStream {
+    let ident = field.ident.as_ref().unwrap();
+
+    let mut linear_config = Vec::new();
+    let mut conv_args = None;
+
+    for attr in field.attrs.iter().filter(|attr| is_config_attr(attr)) {
+        let option = if attr.path.is_ident("init") {
+            attr.parse_args::<Expr>()
+                .map(|init| quote!(init: ::std::boxed::Box::new(#init),))
+        } else if attr.path.is_ident("bias") {
+            attr.parse_args::<LitBool>()
+                .map(|bias| quote!(bias: #bias,))
+        } else if attr.path.is_ident("bias_init") {
+            attr.parse_args::<Expr>()
+                .map(|bias_init| quote!(bias_init: #bias_init,))
+        } else if attr.path.is_ident("l2") {
+            attr.parse_args::<Expr>()
+                .map(|l2| quote!(l2_reg: <T as gradients::number::Float>::as_generic(#l2),))
+        } else {
+            match parse_conv(attr) {
+                Ok(args) => conv_args = Some(args),
+                Err(err) => emit_error!(err.span(), err),
+            }
+            continue;
+        };
+
+        match option {
+            Ok(option) => linear_config.push(option),
+            Err(err) => emit_error!(err.span(), err),
+        }
+    }
+
+    if !linear_config.is_empty() {
+        if shape::linear_sizes(&field.ty).is_none() {
+            emit_error!(
+                ident,
+                "`#[init]`, `#[bias]`, `#[bias_init]` and `#[l2]` can only be applied on `Linear` fields."
+            );
+        } else {
+            return quote! {
+                gradients::linear::Linear::new(device, gradients::linear::LinearConfig {
+                    #(#linear_config)*
+                    ..::core::default::Default::default()
+                })
+            };
+        }
+    }
+
+    if let Some(conv_args) = conv_args {
+        if !is_conv(&field.ty) {
+            emit_error!(ident, "`#[conv]` can only be applied on `Conv2D` fields.");
+        } else {
+            return quote!(gradients::Conv2D::new(device, #conv_args));
+        }
+    }
+
+    quote!(gradients::WithDevice::with(device))
+}
@@ -0,0 +1,362 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::{emit_call_site_error, emit_error};
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, token::Comma, Field, Ident, Lit, Meta, NestedMeta};
+
+/// Where the data of a node comes from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The inputs of the network.
+    Inputs,
+    /// The output of the field with this index.
+    Field(usize),
+}
+
+/// A field of the network.
+/// The output of a node is `self.field.forward(input) + residual`.
+pub struct Node {
+    pub ident: Ident,
+    pub input: Source,
+    pub residual: Option<Source>,
+}
+
+impl Node {
+    fn out(&self) -> Ident {
+        format_ident!("__out_{}", self.ident)
+    }
+
+    fn grad(&self) -> Ident {
+        format_ident!("__grad_{}", self.ident)
+    }
+
+    fn dinput(&self) -> Ident {
+        format_ident!("__dinput_{}", self.ident)
+    }
+}
+
+/// The forward graph of a network.
+pub struct Graph {
+    /// The fields, which are not skipped, in declaration order.
+    pub nodes: Vec<Node>,
+    /// The index of the node, whose output is the output of the network.
+    pub output: usize,
+}
+
+fn has_attr(field: &Field, name: &str) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident(name))
+}
+
+pub fn is_skipped(field: &Field) -> bool {
+    has_attr(field, "skip")
+}
+
+/// Splits the fields, which are not skipped, into chains, in which every field takes the output of the previous one.
+/// A field with `#[input(..)]` starts a new chain, because its input is not necessarily the previous field.
+pub fn chains(fields: &Punctuated<Field, Comma>) -> Vec<Punctuated<Field, Comma>> {
+    let mut chains = vec![Punctuated::new()];
+
+    for field in fields.iter().filter(|field| !is_skipped(field)) {
+        if has_attr(field, "input") {
+            chains.push(Punctuated::new());
+        }
+        chains.last_mut().unwrap().push(field.clone());
+    }
+    chains
+}
+
+/// Builds the forward graph of the fields. By default, every field takes the output of the previous field as input.
+///
+/// - `#[skip]` removes a field from the graph, e.g. an auxiliary head, which is called manually.
+/// - `#[input(field)]` or `#[input(inputs)]` takes the output of a previous field or the inputs of the network as input.
+/// - `#[residual(from = "field")]` or `#[residual(from = "inputs")]` adds a skip connection.
+/// - `#[output]` marks the field, whose output is returned. Defaults to the last field, which is not skipped.
+///
+/// Every field in the graph must contribute to the output.
+pub fn build_graph(fields: &Punctuated<Field, Comma>) -> Option<Graph> {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut output = None;
+
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+
+        if is_skipped(field) {
+            for name in ["residual", "input", "output"] {
+                if has_attr(field, name) {
+                    emit_error!(
+                        ident,
+                        "A skipped field cannot be annotated with `#[{}]`.",
+                        name
+                    );
+                }
+            }
+            skipped.push(ident);
+            continue;
+        }
+
+        let mut input = match nodes.len() {
+            0 => Source::Inputs,
+            len => Source::Field(len - 1),
+        };
+        let mut residual = None;
+
+        for attr in &field.attrs {
+            let source = if attr.path.is_ident("residual") {
+                parse_residual(attr, &nodes, &skipped).map(|source| residual = Some(source))
+            } else if attr.path.is_ident("input") {
+                parse_input(attr, &nodes, &skipped).map(|source| input = source)
+            } else if attr.path.is_ident("output") {
+                if output.is_some() {
+                    emit_error!(
+                        attr.path,
+                        "Only one field can be the output of the network."
+                    );
+                }
+                output = Some(nodes.len());
+                Ok(())
+            } else {
+                Ok(())
+            };
+
+            if let Err((span, msg)) = source {
+                emit_error!(span, msg);
+            }
+        }
+
+        nodes.push(Node {
+            ident,
+            input,
+            residual,
+        });
+    }
+
+    if nodes.is_empty() {
+        emit_call_site_error!("A network needs at least one field, which is not skipped.");
+        return None;
+    }
+
+    let graph = Graph {
+        output: output.unwrap_or(nodes.len() - 1),
+        nodes,
+    };
+    check_contributions(&graph).then_some(graph)
+}
+
+/// Emits an error for every node, which does not contribute to the output.
+/// These would never receive a gradient in the backward pass.
+fn check_contributions(graph: &Graph) -> bool {
+    let mut contributes = vec![false; graph.nodes.len()];
+    contributes[graph.output] = true;
+
+    for (idx, node) in graph.nodes.iter().enumerate().rev() {
+        if !contributes[idx] {
+            emit_error!(
+                node.ident,
+                "{:?} does not contribute to the output of the network.", node.ident.to_string();
+                help = "Mark {:?} with `#[skip]` if it is called manually.", node.ident.to_string()
+            );
+            continue;
+        }
+
+        for source in std::iter::once(node.input).chain(node.residual) {
+            if let Source::Field(source) = source {
+                contributes[source] = true;
+            }
+        }
+    }
+    contributes.into_iter().all(|contributes| contributes)
+}
+
+type AttrError = (proc_macro2::Span, String);
+
+/// Finds the node called `name`. Only previous fields, which are not skipped, or the inputs of the network can be used.
+fn find_source(
+    name: &str,
+    span: proc_macro2::Span,
+    nodes: &[Node],
+    skipped: &[Ident],
+) -> Result<Source, AttrError> {
+    if name == "inputs" {
+        return Ok(Source::Inputs);
+    }
+
+    if let Some(idx) = nodes.iter().position(|node| node.ident == name) {
+        return Ok(Source::Field(idx));
+    }
+
+    let msg = if skipped.iter().any(|ident| ident == name) {
+        format!("{:?} is skipped, hence its output is not available.", name)
+    } else {
+        format!(
+            "{:?} is not a previous field. Use a previous field or \"inputs\".",
+            name
+        )
+    };
+    Err((span, msg))
+}
+
+fn parse_input(
+    attr: &syn::Attribute,
+    nodes: &[Node],
+    skipped: &[Ident],
+) -> Result<Source, AttrError> {
+    match attr.parse_args::<Ident>() {
+        Ok(ident) => find_source(&ident.to_string(), ident.span(), nodes, skipped),
+        Err(_) => Err((
+            attr.path.get_ident().unwrap().span(),
+            "expected `#[input(field)]` or `#[input(inputs)]`".to_string(),
+        )),
+    }
+}
+
+fn parse_residual(
+    attr: &syn::Attribute,
+    nodes: &[Node],
+    skipped: &[Ident],
+) -> Result<Source, AttrError> {
+    let usage = "expected `#[residual(from = \"field\")]`".to_string();
+
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => return Err((attr.path.get_ident().unwrap().span(), usage)),
+    };
+
+    for nested in list.nested.iter() {
+        if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+            if !name_value.path.is_ident("from") {
+                continue;
+            }
+
+            return match &name_value.lit {
+                Lit::Str(from) => find_source(&from.value(), from.span(), nodes, skipped),
+                lit => Err((lit.span(), usage)),
+            };
+        }
+    }
+    Err((list.path.get_ident().unwrap().span(), usage))
+}
+
+/// A network without skip connections or custom inputs, hence the forward and backward passes can be generated as a chain.
+pub fn is_chain(graph: &Graph) -> bool {
+    graph.output == graph.nodes.len() - 1
+        && graph.nodes.iter().enumerate().all(|(idx, node)| {
+            let prev = match idx {
+                0 => Source::Inputs,
+                idx => Source::Field(idx - 1),
+            };
+            node.residual.is_none() && node.input == prev
+        })
+}
+
+fn source_out(nodes: &[Node], source: Source) -> TokenStream {
+    match source {
+        Source::Inputs => quote!(inputs),
+        Source::Field(idx) => {
+            let out = nodes[idx].out();
+            quote!(&#out)
+        }
+    }
+}
+
+pub fn forward(graph: &Graph) -> TokenStream {
+    let nodes = &graph.nodes;
+
+    let steps = nodes
+        .iter()
+        .map(|node| {
+            let ident = &node.ident;
+            let out = node.out();
+            let input = source_out(nodes, node.input);
+
+            match node.residual {
+                Some(residual) => {
+                    let residual = source_out(nodes, residual);
+                    quote!(let #out = self.#ident.forward(#input) + #residual;)
+                }
+                None => quote!(let #out = self.#ident.forward(#input);),
+            }
+        })
+        .collect::<TokenStream>();
+
+    let output = nodes[graph.output].out();
+
+    quote! {
+        #steps
+        #output
+    }
+}
+
+/// Sums up the gradients, which flow into the same output.
+/// The first summand needs to be an owned matrix.
+fn sum_grads(mut owned: Vec<TokenStream>, borrowed: Vec<Ident>) -> TokenStream {
+    let mut borrowed = borrowed.into_iter();
+
+    let first = if owned.is_empty() {
+        let first = borrowed.next().unwrap();
+        quote!(#first.shallow_or_clone())
+    } else {
+        owned.remove(0)
+    };
+
+    let rest = owned
+        .into_iter()
+        .map(|grad| quote!(+ &#grad))
+        .chain(borrowed.map(|grad| quote!(+ &#grad)));
+
+    quote!(#first #(#rest)*)
+}
+
+/// Collects the gradients of every consumer of `source`.
+/// A consumer that takes `source` as input contributes the gradient of its backward pass,
+/// a residual connection passes the gradient of the consumer's output directly.
+fn grads_of(nodes: &[Node], source: Source) -> (Vec<TokenStream>, Vec<Ident>) {
+    let mut owned = Vec::new();
+    let mut borrowed = Vec::new();
+
+    for node in nodes {
+        if node.input == source {
+            let dinput = node.dinput();
+            owned.push(quote!(#dinput));
+        }
+        if node.residual == Some(source) {
+            borrowed.push(node.grad());
+        }
+    }
+    (owned, borrowed)
+}
+
+/// Generates the backward pass. The gradient of the network enters at the output node.
+/// The gradients of nodes, whose output is used more than once, are accumulated.
+pub fn backward(graph: &Graph) -> TokenStream {
+    let nodes = &graph.nodes;
+
+    let steps = nodes
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(idx, node)| {
+            let ident = &node.ident;
+            let grad = node.grad();
+            let dinput = node.dinput();
+
+            let (mut owned, borrowed) = grads_of(nodes, Source::Field(idx));
+            if idx == graph.output {
+                owned.insert(0, quote!(grad.shallow_or_clone()));
+            }
+            let sum = sum_grads(owned, borrowed);
+
+            quote! {
+                let #grad = #sum;
+                let #dinput = self.#ident.backward(&#grad);
+            }
+        })
+        .collect::<TokenStream>();
+
+    let (owned, borrowed) = grads_of(nodes, Source::Inputs);
+    let dinputs = sum_grads(owned, borrowed);
+
+    quote! {
+        #steps
+        #dinputs
+    }
+}
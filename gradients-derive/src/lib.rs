@@ -1,12 +1,27 @@
 extern crate proc_macro;
-use proc_macro2::{TokenStream, TokenTree};
+mod config;
+mod graph;
+mod shape;
+mod summary;
+
+use proc_macro2::TokenStream;
 use proc_macro_error::{emit_error, proc_macro_error};
-use quote::{quote, ToTokens};
+use quote::quote;
 use syn::{
-    parse_macro_input, punctuated::Punctuated, token::Comma, Data, DeriveInput, Field, Fields,
-    Ident,
+    parse_macro_input, parse_quote, punctuated::Punctuated, token::Comma, Data, DeriveInput, Field,
+    Fields, Generics, Ident, PathArguments, Type,
 };
 
+/// Turns a struct of layers into a neural network.
+///
+/// The lifetime and datatype parameters are added to the struct and to every layer,
+/// hence `Linear<784, 128>` becomes `Linear<'a, T, 784, 128>`.
+/// Visibility, attributes and additional generic parameters of the struct are kept.
+///
+/// `NeuralNetwork` is derived and `WithDevice` is implemented, which constructs every layer on the given device.
+/// `with` panics if the sizes of the layers don't fit together. Layers, which don't implement `LayerShape`, are not checked.
+/// The construction of a field can be configured with `#[init(..)]`, `#[bias(..)]`, `#[bias_init(..)]`, `#[l2(..)]` (`Linear`)
+/// and `#[conv(input = .., kernel = .., blocks = ..)]` (`Conv2D`).
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn network(
@@ -14,106 +29,157 @@ pub fn network(
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let name = input.ident;
 
-    let fields = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => fields.named,
-            _ => panic!("The network attribute can be applied on structs only."),
+    let fields = match named_fields(&input, "The network attribute") {
+        Some(fields) => fields.clone(),
+        None => return proc_macro::TokenStream::new(),
+    };
+
+    proc_macro::TokenStream::from(add_lifetimes_derive_net(input, fields))
+}
+
+/// Returns the named fields of a struct. Emits an error for other items.
+fn named_fields<'a>(input: &'a DeriveInput, what: &str) -> Option<&'a Punctuated<Field, Comma>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => return Some(&fields.named),
+            fields => emit_error!(
+                fields,
+                "{} can be applied on structs with named fields only.",
+                what
+            ),
         },
-        _ => panic!("The network attribute can be applied on structs only."),
+        _ => emit_error!(input.ident, "{} can be applied on structs only.", what),
+    }
+    None
+}
+
+/// Adds the lifetime and datatype parameter to a layer, e.g. `ReLU` becomes `ReLU<'a, T>`.
+fn add_lifetimes(ty: &Type) -> TokenStream {
+    let mut path = match ty {
+        Type::Path(path) => path.clone(),
+        _ => {
+            emit_error!(ty, "Expected a layer type, e.g. `Linear<8, 16>` or `ReLU`.");
+            return quote!(#ty);
+        }
     };
 
-    proc_macro::TokenStream::from(add_lifetimes_derive_net(name, fields))
+    let segment = path.path.segments.last_mut().unwrap();
+    match &mut segment.arguments {
+        PathArguments::AngleBracketed(args) => {
+            args.args.insert(0, parse_quote!('a));
+            args.args.insert(1, parse_quote!(T));
+        }
+        PathArguments::None => {
+            segment.arguments = PathArguments::AngleBracketed(parse_quote!(<'a, T>))
+        }
+        PathArguments::Parenthesized(args) => {
+            emit_error!(
+                args,
+                "Expected a layer type, e.g. `Linear<8, 16>` or `ReLU`."
+            )
+        }
+    }
+
+    quote!(#path)
 }
 
-fn add_lifetimes_derive_net(name: Ident, fields: Punctuated<Field, Comma>) -> TokenStream {
-    let mut prev_out_size_info = (None, None);
+fn add_lifetimes_derive_net(input: DeriveInput, fields: Punctuated<Field, Comma>) -> TokenStream {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident: name,
+        mut generics,
+        ..
+    } = input;
 
     let fields_with_lifetimes = fields
         .iter()
         .map(|f| {
-            let mut in_or_out_size = 0;
-
             let name = &f.ident;
-            let t = &f.ty;
-            let type_token = t.into_token_stream();
-
-            if type_token.to_string().starts_with("Linear") {
-                let mut in_out_size = TokenStream::new();
-                for token in type_token {
-                    if let TokenTree::Literal(lit) = &token {
-                        let lit_tokens = lit.to_token_stream();
-
-                        in_out_size.extend(lit_tokens.clone());
-                        
-                        if in_or_out_size == 1 {
-                            prev_out_size_info = (Some(lit_tokens.clone()), name.clone());
-                        }
-                        
-                        // comparing the output size with the next input size of the linear layer
-                        if let Some(prev_out_size) = &prev_out_size_info.0 {
-                            if in_or_out_size == 0 && prev_out_size.to_string() != lit_tokens.to_string() {
-                                emit_error! { lit_tokens,
-                                    format!("The output and input size of {prev_ident:?} (output size: {prev_out}) and {ident:?} (input size: {input}) do not match.",
-                                 
-                                        prev_ident=prev_out_size_info.1.as_ref().unwrap(), 
-                                        prev_out=prev_out_size, 
-                                        ident=name.as_ref().unwrap(), 
-                                        input=lit_tokens
-                                    );                              
-                                    note=format!("The input size of {ident:?} must be equal to the output size of {prev_ident:?}.",
-                                            ident=name.as_ref().unwrap(), 
-                                            prev_ident=prev_out_size_info.1.as_ref().unwrap(), 
-                                    );
-                                    help=format!("Set the input size of {ident:?} to {prev_out}.",
-                                        ident=name.as_ref().unwrap(),
-                                        prev_out=prev_out_size, 
-                                    );
-                                }
-                            }
-                        }
-                        in_or_out_size += 1;
-                    }
-
-                    if let TokenTree::Punct(pun) = token {
-                        if pun.as_char() != ',' {
-                            continue;
-                        }
-                        in_out_size.extend(pun.to_token_stream());
-                    }
-                }
-
-                quote! {#name: Linear<'a, T, #in_out_size>,}
-            } else {
-                quote!(#name: #t<'a, T>,)
-            }
+            let vis = &f.vis;
+            let attrs = f.attrs.iter().filter(|attr| !config::is_config_attr(attr));
+            let t = add_lifetimes(&f.ty);
+
+            quote!(#(#attrs)* #vis #name: #t,)
         })
         .collect::<TokenStream>();
 
+    // the sizes are checked along the chains of fields, which are not skipped.
+    // The edges into fields with custom inputs are not checked.
+    let chains = graph::chains(&fields);
+
+    let size_assertions = chains
+        .iter()
+        .map(|chain| shape::check_linear_sizes(chain, &generics))
+        .collect::<TokenStream>();
+
     let with_device_chain = fields
         .iter()
         .map(|f| {
             let name = &f.ident;
+            let construct = config::construct_field(f);
 
-            quote!(#name: WithDevice::with(device),)
+            quote!(#name: #construct,)
         })
         .collect::<TokenStream>();
 
+    let shape_checks = chains
+        .iter()
+        .map(|chain| {
+            let layer_shapes = chain
+                .iter()
+                .map(|f| {
+                    let name = &f.ident;
+                    let name_str = name.as_ref().unwrap().to_string();
+                    let shape = shape::layer_shape(quote!(network.#name));
+
+                    quote!((#name_str, #shape),)
+                })
+                .collect::<TokenStream>();
+
+            quote!(gradients::check_shapes(&[#layer_shapes]);)
+        })
+        .collect::<TokenStream>();
+
+    // lifetimes have to be declared before type parameters
+    let lifetimes = generics.lifetimes().count();
+    generics.params.insert(0, parse_quote!('a));
+    generics.params.insert(lifetimes + 1, parse_quote!(T));
+    let where_clause = &generics.where_clause;
+
+    // Conv2D::new requires T: CDatatype
+    let bounds = if config::has_conv(&fields) {
+        quote!(gradients::number::Float + gradients::CDatatype)
+    } else {
+        quote!(gradients::number::Float)
+    };
+
+    let mut with_generics = generics.clone();
+    with_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(T: #bounds));
+    let (impl_generics, ty_generics, with_where_clause) = with_generics.split_for_impl();
+
     quote! {
-        use gradients::{NeuralNetwork, Alloc, WithDevice, number::Float, GraphReturn};
-        #[derive(NeuralNetwork)]
-        struct #name<'a, T> {
+        #(#attrs)*
+        #[derive(gradients::NeuralNetwork)]
+        #vis struct #name #generics #where_clause {
             #fields_with_lifetimes
         }
-        impl<'a, T: Float> WithDevice<'a, T> for #name<'a, T> {
-            fn with<'b: 'a, D: Alloc<T>+GraphReturn>(device: &'b D) -> Self {
-                Self { #with_device_chain }
+        #size_assertions
+        impl #impl_generics gradients::WithDevice<'a, T> for #name #ty_generics #with_where_clause {
+            fn with<'b: 'a, D: gradients::Alloc<T> + gradients::GraphReturn>(device: &'b D) -> Self {
+                let network = Self { #with_device_chain };
+                #shape_checks
+                network
             }
         }
     }
 }
 
+/// Implements the traits of a layer without parameters.
 #[proc_macro_derive(NoParams)]
 pub fn derive_params(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -124,59 +190,116 @@ pub fn derive_params(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 fn impl_params(name: Ident) -> TokenStream {
     quote! {
-        impl<'a, T> GetParam<'a, T> for #name<'a, T> {}
-        impl<'a, T> WithDevice<'a, T> for #name<'a, T> {}
+        impl<'a, T> gradients::GetParam<'a, T> for #name<'a, T> {}
+        impl<'a, T> gradients::WithDevice<'a, T> for #name<'a, T> {}
+        impl<'a, T> gradients::LayerShape for #name<'a, T> {}
         impl<'a, T> #name<'a, T> {
             pub fn with_device<'b, D>(_dev: &'b D) -> #name<'a, T> {
-                Self::default()
+                <Self as ::core::default::Default>::default()
             }
         }
     }
 }
 
-#[proc_macro_derive(NeuralNetwork)]
+/// Implements `NeuralNetwork` for a struct of layers. The output of each field is passed to the next field.
+///
+/// A field with `#[residual(from = "field")]` adds the output of a previous field (or of the network `"inputs"`)
+/// to its own output. In the backward pass, the gradient is passed through the skip connection as well.
+///
+/// Other graphs can be described with:
+/// - `#[input(field)]` or `#[input(inputs)]`: The field takes the output of a previous field or the network inputs.
+/// - `#[output]`: The output of the field is the output of the network. Defaults to the last field.
+/// - `#[skip]`: The field is neither called in the forward and backward pass nor are its parameters returned.
+///
+/// If the output of a field is used by several fields, their gradients are summed up in the backward pass.
+///
+/// `summary_table` lists every field with its sizes and number of parameters.
+/// Fields, which don't implement `LayerShape`, are listed with unknown sizes.
+#[proc_macro_derive(NeuralNetwork, attributes(residual, input, output, skip))]
+#[proc_macro_error]
 pub fn derive_neural_network(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = input.ident;
-    let fields = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => fields.named,
-            _ => panic!("Structs only"),
-        },
-        _ => panic!("Structs only"),
+    let fields = match named_fields(&input, "NeuralNetwork") {
+        Some(fields) => fields.clone(),
+        None => return proc_macro::TokenStream::new(),
     };
 
-    proc_macro::TokenStream::from(impl_neural_network(name, fields))
+    proc_macro::TokenStream::from(impl_neural_network(input.ident, input.generics, fields))
 }
 
-fn impl_neural_network(name: Ident, fields: Punctuated<Field, Comma>) -> TokenStream {
-    let forward_chain = fields.iter().fold(quote!(&inputs), |acc, f| {
-        let name = &f.ident;
-        quote!(self.#name.forward(&#acc))
-    });
+fn impl_neural_network(
+    name: Ident,
+    generics: Generics,
+    fields: Punctuated<Field, Comma>,
+) -> TokenStream {
+    let (lifetime, datatype) = match (generics.lifetimes().next(), generics.type_params().next()) {
+        (Some(lifetime), Some(datatype)) => (lifetime.lifetime.clone(), datatype.ident.clone()),
+        _ => {
+            emit_error!(
+                name,
+                "A network needs a lifetime and a datatype parameter, e.g. `struct Net<'a, T>`."
+            );
+            return TokenStream::new();
+        }
+    };
 
     let default_chain = fields
         .iter()
         .map(|f| {
             let name = &f.ident;
-            quote!(#name: Default::default(),)
+            quote!(#name: ::core::default::Default::default(),)
         })
         .collect::<TokenStream>();
 
-    let backward_chain = fields.iter().rev().fold(quote!(&grad), |acc, f| {
-        let name = &f.ident;
-        quote!(self.#name.backward(&#acc))
-    });
+    let mut default_generics = generics.clone();
+    default_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#datatype: gradients::number::Number));
+    let (impl_generics, ty_generics, where_clause) = default_generics.split_for_impl();
+
+    let default_impl = quote! {
+        impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self { #default_chain }
+            }
+        }
+    };
+
+    let graph = match graph::build_graph(&fields) {
+        Some(graph) => graph,
+        None => return default_impl,
+    };
+
+    let forward_chain = if graph::is_chain(&graph) {
+        graph.nodes.iter().fold(quote!(&inputs), |acc, node| {
+            let name = &node.ident;
+            quote!(self.#name.forward(&#acc))
+        })
+    } else {
+        graph::forward(&graph)
+    };
+
+    let backward_chain = if graph::is_chain(&graph) {
+        graph.nodes.iter().rev().fold(quote!(&grad), |acc, node| {
+            let name = &node.ident;
+            quote!(self.#name.backward(&#acc))
+        })
+    } else {
+        graph::backward(&graph)
+    };
 
-    let vec = quote! {let mut vec = Vec::new();};
+    let vec = quote! {let mut vec = ::std::vec::Vec::new();};
 
-    let params = fields
+    // skipped fields are not part of the backward pass, hence they have no gradients
+    let params = graph
+        .nodes
         .iter()
-        .map(|f| {
-            let name = &f.ident;
+        .map(|node| {
+            let name = &node.ident;
             quote!(
-               if let Some(params) = self.#name.params() {
+               if let ::core::option::Option::Some(params) = gradients::GetParam::params(&mut self.#name) {
                    vec.push(params);
                }
             )
@@ -184,29 +307,54 @@ fn impl_neural_network(name: Ident, fields: Punctuated<Field, Comma>) -> TokenSt
         .collect::<TokenStream>();
     let return_vec = quote! {vec};
 
+    let layer_shapes = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let name = &node.ident;
+            let shape = shape::layer_shape(quote!(self.#name));
+
+            quote!(#shape,)
+        })
+        .collect::<TokenStream>();
+
+    let summary_table = summary::summary_table(&graph, &fields, &lifetime, &datatype);
+
+    let mut nn_generics = generics.clone();
+    nn_generics.make_where_clause().predicates.push(parse_quote!(
+        #datatype: gradients::number::Float + gradients::CDatatype + gradients::GenericBlas + gradients::CudaTranspose
+    ));
+    let (impl_generics, ty_generics, where_clause) = nn_generics.split_for_impl();
+
     quote! {
-        use gradients::{GetParam, Param, Matrix, number::Number};
+        // the generated code doesn't rely on this import, it is only kept for compatibility, see `gradients::derive_prelude`
+        #[allow(unused_imports)]
+        use gradients::derive_prelude::*;
 
+        #default_impl
 
-        impl<'a, T: Number> Default for #name<'a, T> {
-            fn default() -> Self {
-                Self { #default_chain }
-            }
-        }
-        impl<'a, T: gradients::number::Float+gradients::CDatatype+gradients::GenericBlas + gradients::CudaTranspose> NeuralNetwork<'a, T> for #name<'a, T> {
-            fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        impl #impl_generics gradients::NeuralNetwork<#lifetime, #datatype> for #name #ty_generics #where_clause {
+            fn forward(&mut self, inputs: &gradients::Matrix<#lifetime, #datatype>) -> gradients::Matrix<#lifetime, #datatype> {
                 #forward_chain
             }
 
-            fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+            fn backward(&mut self, grad: &gradients::Matrix<#lifetime, #datatype>) -> gradients::Matrix<#lifetime, #datatype> {
                 #backward_chain
             }
 
-            fn params(&mut self) -> Vec<Param<'a, T>> {
+            fn params(&mut self) -> ::std::vec::Vec<gradients::Param<#lifetime, #datatype>> {
                 #vec
                 #params
                 #return_vec
             }
+
+            fn summary_table(&self) -> gradients::Summary {
+                #summary_table
+            }
+
+            fn layer_shapes(&self) -> ::std::vec::Vec<&dyn gradients::LayerShape> {
+                ::std::vec![#layer_shapes]
+            }
         }
     }
 }
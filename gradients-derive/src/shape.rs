@@ -0,0 +1,126 @@
+use proc_macro2::{TokenStream, TokenTree};
+use proc_macro_error::emit_error;
+use quote::{quote, ToTokens};
+use syn::{
+    punctuated::Punctuated, token::Comma, Expr, ExprLit, Field, GenericArgument, Generics, Ident,
+    Lit, PathArguments, Type,
+};
+
+/// Layers, which keep the size of their inputs.
+const SIZE_PRESERVING: [&str; 5] = ["ReLU", "Tanh", "Sigmoid", "Softmax", "LogSoftmax"];
+
+fn layer_name(ty: &Type) -> Option<&syn::PathSegment> {
+    match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    }
+}
+
+/// Returns the input and output size of a `Linear<I, O>` field.
+pub fn linear_sizes(ty: &Type) -> Option<(GenericArgument, GenericArgument)> {
+    let segment = layer_name(ty)?;
+    if segment.ident != "Linear" {
+        return None;
+    }
+
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+
+    let mut sizes = args.iter().cloned();
+    Some((sizes.next()?, sizes.next()?))
+}
+
+fn int_lit(size: &GenericArgument) -> Option<usize> {
+    match size {
+        GenericArgument::Const(Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        })) => int.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+fn uses_generics(size: &GenericArgument, generics: &Generics) -> bool {
+    fn contains(tokens: TokenStream, idents: &[&Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => idents.contains(&&ident),
+            TokenTree::Group(group) => contains(group.stream(), idents),
+            _ => false,
+        })
+    }
+
+    let idents = generics
+        .const_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    contains(size.to_token_stream(), &idents)
+}
+
+/// Returns the shape of a layer as `&dyn LayerShape`.
+/// Layers, which don't implement `LayerShape`, have an unknown shape instead of failing to compile.
+pub fn layer_shape(layer: TokenStream) -> TokenStream {
+    quote!({
+        #[allow(unused_imports)]
+        use gradients::{HasShape as _, NoShape as _};
+        (&gradients::ShapeOf(&#layer)).layer_shape()
+    })
+}
+
+/// Checks if the output size of a `Linear` field matches the input size of the next `Linear` field.
+/// Only size preserving layers (e.g. activation functions) may lie in between.
+///
+/// Literal sizes are compared right away.
+/// Computed sizes (e.g. `{5 * 26 * 26}`) are compared at compile time by the returned const assertions.
+/// Sizes, which depend on the generic parameters of the network, cannot be checked this way and are skipped.
+pub fn check_linear_sizes(fields: &Punctuated<Field, Comma>, generics: &Generics) -> TokenStream {
+    let mut prev: Option<(&Ident, GenericArgument)> = None;
+    let mut assertions = TokenStream::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+
+        let (input, output) = match linear_sizes(&field.ty) {
+            Some(sizes) => sizes,
+            None => {
+                let preserves_size = layer_name(&field.ty).is_some_and(|segment| {
+                    SIZE_PRESERVING.iter().any(|name| segment.ident == name)
+                });
+                if !preserves_size {
+                    prev = None;
+                }
+                continue;
+            }
+        };
+
+        if let Some((prev_ident, prev_output)) = prev {
+            match (int_lit(&prev_output), int_lit(&input)) {
+                (Some(prev_out), Some(input_size)) if prev_out != input_size => {
+                    emit_error! { input,
+                        "The output and input size of {:?} (output size: {}) and {:?} (input size: {}) do not match.",
+                        prev_ident.to_string(), prev_out, ident.to_string(), input_size;
+                        note = "The input size of {:?} must be equal to the output size of {:?}.", ident.to_string(), prev_ident.to_string();
+                        help = "Set the input size of {:?} to {}.", ident.to_string(), prev_out;
+                    }
+                }
+                (Some(_), Some(_)) => (),
+                _ if uses_generics(&prev_output, generics) || uses_generics(&input, generics) => (),
+                _ => {
+                    let msg = format!(
+                        "The output size of {:?} does not match the input size of {:?}.",
+                        prev_ident.to_string(),
+                        ident.to_string()
+                    );
+                    assertions.extend(quote! {
+                        const _: () = assert!(#prev_output == #input, #msg);
+                    });
+                }
+            }
+        }
+
+        prev = Some((ident, output));
+    }
+
+    assertions
+}
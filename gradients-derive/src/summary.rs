@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    punctuated::Punctuated, token::Comma, Field, GenericArgument, Ident, Lifetime, PathArguments,
+    Type,
+};
+
+use crate::{
+    graph::{Graph, Source},
+    shape::layer_shape,
+};
+
+/// The type of a layer as it is written by the user, e.g. `Linear<784, 128>` for `Linear<'a, T, 784, 128>`.
+fn layer_name(ty: &Type, lifetime: &Lifetime, datatype: &Ident) -> String {
+    let mut ty = ty.clone();
+
+    if let Type::Path(path) = &mut ty {
+        if let Some(segment) = path.path.segments.last_mut() {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                args.args = args
+                    .args
+                    .iter()
+                    .filter(|arg| match arg {
+                        GenericArgument::Lifetime(arg) => arg != lifetime,
+                        GenericArgument::Type(Type::Path(arg)) => !arg.path.is_ident(datatype),
+                        _ => true,
+                    })
+                    .cloned()
+                    .collect();
+
+                if args.args.is_empty() {
+                    segment.arguments = PathArguments::None;
+                }
+            }
+        }
+    }
+
+    ty.to_token_stream()
+        .to_string()
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+}
+
+/// Generates the body of `NeuralNetwork::summary_table`.
+///
+/// The input size of a field is its own input size or, if it accepts any size, the output size of its input in the graph.
+/// Skipped fields are not connected, hence only their own sizes are known.
+pub fn summary_table(
+    graph: &Graph,
+    fields: &Punctuated<Field, Comma>,
+    lifetime: &Lifetime,
+    datatype: &Ident,
+) -> TokenStream {
+    let input_size = |ident: &Ident| format_ident!("__input_size_{}", ident);
+    let output_size = |ident: &Ident| format_ident!("__output_size_{}", ident);
+
+    let sizes = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let node = graph.nodes.iter().find(|node| node.ident == *ident);
+
+            let source = match node.map(|node| node.input) {
+                Some(Source::Field(idx)) => {
+                    let size = output_size(&graph.nodes[idx].ident);
+                    quote!(#size)
+                }
+                _ => quote!(::core::option::Option::None),
+            };
+
+            let input = input_size(ident);
+            let output = output_size(ident);
+            let shape = layer_shape(quote!(self.#ident));
+
+            quote! {
+                let #input = #shape.input_size().or(#source);
+                let #output = #shape.output_size(#input);
+            }
+        })
+        .collect::<TokenStream>();
+
+    let rows = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let name = ident.to_string();
+            let layer = layer_name(&field.ty, lifetime, datatype);
+            let input = input_size(ident);
+            let output = output_size(ident);
+            let shape = layer_shape(quote!(self.#ident));
+
+            quote! {
+                gradients::LayerSummary {
+                    name: #name,
+                    layer: #layer,
+                    input_size: #input,
+                    output_size: #output,
+                    params: #shape.param_count(),
+                },
+            }
+        })
+        .collect::<TokenStream>();
+
+    quote! {
+        #sizes
+        gradients::Summary::new(::std::vec![#rows])
+    }
+}
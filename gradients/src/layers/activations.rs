@@ -1,4 +1,4 @@
-use crate::{GetParam, WithDevice};
+use crate::Layer;
 use custos::{number::Float, CDatatype, GenericBlas};
 use custos_math::Matrix;
 use gradients_derive::NoParams;
@@ -29,6 +29,16 @@ impl<'a, T> Default for ReLU<'a, T> {
     }
 }
 
+impl<'a, T: Float + CDatatype> Layer<'a, T> for ReLU<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        ReLU::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        ReLU::backward(self, grad)
+    }
+}
+
 #[derive(NoParams)]
 pub struct Softmax<'a, T> {
     activated: Option<Matrix<'a, T>>,
@@ -58,6 +68,16 @@ impl<'a, T> Default for Softmax<'a, T> {
     }
 }
 
+impl<'a, T: CDatatype + GenericBlas> Layer<'a, T> for Softmax<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Softmax::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Softmax::backward(self, grad)
+    }
+}
+
 #[derive(NoParams)]
 pub struct Tanh<'a, T> {
     inputs: Option<Matrix<'a, T>>,
@@ -87,6 +107,16 @@ impl<'a, T> Default for Tanh<'a, T> {
     }
 }
 
+impl<'a, T: Float + CDatatype> Layer<'a, T> for Tanh<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Tanh::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Tanh::backward(self, grad)
+    }
+}
+
 /* 
 
 #[derive(NoParams)]
@@ -1,7 +1,6 @@
-use crate::{GetParam, WithDevice};
+use crate::{rng::rand_matrix, GetParam, Layer, LayerShape, WithDevice};
 use custos::{cached, get_device, number::Float, Alloc, CDatatype, CacheBuf, Device, GraphReturn};
 use custos_math::{correlate_valid_mut, Matrix};
-use gradients_derive::NoParams;
 
 pub struct KernelBlock<'a, T> {
     pub weights: Matrix<'a, T>,
@@ -18,18 +17,14 @@ impl<'a, T> KernelBlock<'a, T> {
     where
         T: Float,
     {
-        let mut weights = Matrix::new(device, shape);
-        weights.rand(T::one().neg(), T::one());
-
-        let mut bias = Matrix::new(device, bias_shape);
-        bias.rand(T::one().neg(), T::one());
+        let weights = rand_matrix(device, shape, T::one().neg(), T::one());
+        let bias = rand_matrix(device, bias_shape, T::one().neg(), T::one());
 
         KernelBlock { weights, bias }
     }
 }
 
 #[doc(hidden)]
-#[derive(NoParams)]
 pub struct Conv2D<'a, T> {
     pub kernel_shape: (usize, usize),
     input_shape: (usize, usize),
@@ -152,3 +147,47 @@ impl<'a, T> Default for Conv2D<'a, T> {
         }
     }
 }
+
+// The traits are implemented by hand instead of deriving NoParams, because the shape depends on the kernels.
+impl<'a, T> GetParam<'a, T> for Conv2D<'a, T> {}
+impl<'a, T> WithDevice<'a, T> for Conv2D<'a, T> {}
+
+impl<'a, T> Conv2D<'a, T> {
+    pub fn with_device<'b, D>(_dev: &'b D) -> Conv2D<'a, T> {
+        Self::default()
+    }
+}
+
+/// A default constructed `Conv2D` layer has no kernels, hence its sizes are unknown.
+impl<'a, T> LayerShape for Conv2D<'a, T> {
+    fn input_size(&self) -> Option<usize> {
+        if self.kernels.is_empty() {
+            return None;
+        }
+        Some(self.input_shape.0 * self.input_shape.1)
+    }
+
+    fn output_size(&self, _input_size: Option<usize>) -> Option<usize> {
+        if self.kernels.is_empty() {
+            return None;
+        }
+        Some(self.kernels.len() * self.output_shape.0 * self.output_shape.1)
+    }
+
+    /// Every kernel block consists of the kernel weights and a bias for each output value.
+    fn param_count(&self) -> usize {
+        let (kernel_rows, kernel_cols) = self.kernel_shape;
+        let (out_rows, out_cols) = self.output_shape;
+        self.kernels.len() * (kernel_rows * kernel_cols + out_rows * out_cols)
+    }
+}
+
+impl<'a, T: Float + CDatatype> Layer<'a, T> for Conv2D<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Conv2D::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Conv2D::backward(self, grad)
+    }
+}
@@ -1,17 +1,22 @@
 mod config;
+mod dyn_linear;
 mod init;
 mod l2_reg;
 
 use std::cell::RefCell;
 
 pub use config::*;
-pub use init::{Glorot, RandomUniform};
+pub use dyn_linear::*;
+pub use init::{
+    BiasInit, DynInit, FanMode, Glorot, Init, KaimingNormal, KaimingUniform, LeCunNormal,
+    Nonlinearity, Orthogonal, RandomUniform, TruncatedNormal, XavierNormal,
+};
 pub use l2_reg::*;
 
 use custos::{number::Float, Alloc, CDatatype, GenericBlas, GraphReturn};
 use custos_math::{CudaTranspose, Matrix};
 
-use crate::{GetParam, Param, WithDevice};
+use crate::{GetParam, Layer, LayerShape, Param, WithDevice};
 
 type LinearParams<'a, T> = (Matrix<'a, T>, Option<Matrix<'a, T>>);
 
@@ -67,47 +72,88 @@ impl<'a, T: Copy + Float, const I: usize, const O: usize> WithDevice<'a, T>
 impl<'a, T: Float + GenericBlas + CDatatype, const I: usize, const O: usize> Linear<'a, T, I, O> {
     pub fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
         self.inputs = Some(inputs.shallow_or_clone());
-        let mut forward = inputs.gemm(&self.weights);
-
-        if let Some(bias) = &self.bias {
-            forward.add_row_mut(bias);
-        }
-
-        // l2 reg loss
-        if let Some(l2_reg_loss) = self.l2_reg_loss {
-            let mut l2_reg_loss = l2_reg_loss.borrow_mut();
-
-            *l2_reg_loss += (&self.weights * &self.weights).sum() * self.l2_reg;
-
-            if let Some(bias) = &self.bias {
-                *l2_reg_loss += (bias * bias).sum() * self.l2_reg;
-            }
-        }
-
-        forward
+        forward(
+            inputs,
+            &self.weights,
+            self.bias.as_ref(),
+            self.l2_reg,
+            self.l2_reg_loss,
+        )
     }
 
     pub fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T>
     where
         T: CudaTranspose,
     {
-        if self.bias.is_some() {
-            self.dbias = Some(grad.sum_rows());
-        }
+        let (dweights, dbias) = param_grads(
+            self.inputs.as_ref().unwrap(),
+            grad,
+            &self.weights,
+            self.bias.as_ref(),
+            self.l2_reg,
+        );
+        self.dweights = Some(dweights);
+        self.dbias = dbias;
 
-        let mut dweights = self.inputs.as_ref().unwrap().T().gemm(grad);
+        grad.gemm(&self.weights.T())
+    }
+}
+
+/// The forward pass of a linear layer, shared by [`Linear`] and [`DynLinear`].
+/// Adds the l2 regularization loss of the parameters to `l2_reg_loss`.
+fn forward<'a, T>(
+    inputs: &Matrix<'a, T>,
+    weights: &Matrix<'a, T>,
+    bias: Option<&Matrix<'a, T>>,
+    l2_reg: T,
+    l2_reg_loss: Option<&RefCell<T>>,
+) -> Matrix<'a, T>
+where
+    T: Float + GenericBlas + CDatatype,
+{
+    let mut forward = inputs.gemm(weights);
+
+    if let Some(bias) = bias {
+        forward.add_row_mut(bias);
+    }
+
+    // l2 reg loss
+    if let Some(l2_reg_loss) = l2_reg_loss {
+        let mut l2_reg_loss = l2_reg_loss.borrow_mut();
 
-        if self.l2_reg > T::zero() {
-            dweights += &self.weights * (self.l2_reg * T::two());
+        *l2_reg_loss += (weights * weights).sum() * l2_reg;
 
-            if let Some(dbias) = &mut self.dbias {
-                *dbias += self.bias.as_ref().unwrap() * (self.l2_reg * T::two())
-            }
+        if let Some(bias) = bias {
+            *l2_reg_loss += (bias * bias).sum() * l2_reg;
         }
-        self.dweights = Some(dweights);
+    }
 
-        grad.gemm(&self.weights.T())
+    forward
+}
+
+/// Returns the gradients of the weights and the bias of a linear layer.
+fn param_grads<'a, T>(
+    inputs: &Matrix<'a, T>,
+    grad: &Matrix<'a, T>,
+    weights: &Matrix<'a, T>,
+    bias: Option<&Matrix<'a, T>>,
+    l2_reg: T,
+) -> (Matrix<'a, T>, Option<Matrix<'a, T>>)
+where
+    T: Float + GenericBlas + CDatatype + CudaTranspose,
+{
+    let mut dbias = bias.map(|_| grad.sum_rows());
+    let mut dweights = inputs.T().gemm(grad);
+
+    if l2_reg > T::zero() {
+        dweights += weights * (l2_reg * T::two());
+
+        if let Some(dbias) = &mut dbias {
+            *dbias += bias.unwrap() * (l2_reg * T::two())
+        }
     }
+
+    (dweights, dbias)
 }
 
 impl<'a, T: Copy, const I: usize, const O: usize> GetParam<'a, T> for Linear<'a, T, I, O> {
@@ -116,11 +162,25 @@ impl<'a, T: Copy, const I: usize, const O: usize> GetParam<'a, T> for Linear<'a,
             self.weights.shallow(),
             self.bias.as_ref().map(|bias| bias.shallow()),
             self.dweights.as_ref().unwrap().shallow(),
-            self.dbias.as_ref().unwrap().shallow(),
+            self.dbias.as_ref().map(|dbias| dbias.shallow()),
         ))
     }
 }
 
+impl<'a, T, const I: usize, const O: usize> LayerShape for Linear<'a, T, I, O> {
+    fn input_size(&self) -> Option<usize> {
+        Some(I)
+    }
+
+    fn output_size(&self, _input_size: Option<usize>) -> Option<usize> {
+        Some(O)
+    }
+
+    fn param_count(&self) -> usize {
+        I * O + self.bias.as_ref().map_or(0, |_| O)
+    }
+}
+
 impl<'a, T: Default, const I: usize, const O: usize> Default for Linear<'a, T, I, O> {
     fn default() -> Self {
         Self {
@@ -135,6 +195,19 @@ impl<'a, T: Default, const I: usize, const O: usize> Default for Linear<'a, T, I
     }
 }
 
+impl<'a, T, const I: usize, const O: usize> Layer<'a, T> for Linear<'a, T, I, O>
+where
+    T: Float + GenericBlas + CDatatype + CudaTranspose,
+{
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Linear::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        Linear::backward(self, grad)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::linear::{Glorot, Linear, LinearConfig};
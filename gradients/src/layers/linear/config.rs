@@ -1,19 +1,30 @@
 use std::cell::RefCell;
 
-use super::{init::Init, LinearParams};
+use super::{
+    init::{BiasInit, DynInit, Init},
+    LinearParams,
+};
 use crate::linear::Glorot;
 use custos::{number::Float, Alloc, GraphReturn};
 
 pub struct LinearConfig<'a, T, D, const I: usize, const O: usize> {
     pub init: Box<dyn Init<'a, T, D, I, O>>,
     pub bias: bool,
+    pub bias_init: BiasInit,
     pub l2_reg: T,
     pub l2_reg_loss: Option<&'a RefCell<T>>,
 }
 
 impl<'a, T, D, const I: usize, const O: usize> LinearConfig<'a, T, D, I, O> {
-    pub fn init_params(&self, device: &'a D) -> LinearParams<'a, T> {
-        self.init.init(device, self.bias)
+    pub fn init_params(&self, device: &'a D) -> LinearParams<'a, T>
+    where
+        T: Float,
+        D: Alloc<T> + GraphReturn,
+    {
+        let with_bias = self.bias && self.bias_init.from_init();
+        let (weights, bias) = self.init.init(device, with_bias);
+        let bias = self.bias.then(|| self.bias_init.init(device, O, bias));
+        (weights, bias)
     }
 }
 
@@ -24,6 +35,7 @@ impl<'a, T: Float, D: Alloc<T> + GraphReturn, const I: usize, const O: usize> De
         Self {
             init: Box::new(Glorot),
             bias: true,
+            bias_init: BiasInit::default(),
             l2_reg: T::default(),
             l2_reg_loss: None,
         }
@@ -52,6 +64,21 @@ impl<'a, T, D: 'a, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I,
     }
 }
 
+/// Every boxed initializer, e.g. `Glorot::new()` or `RandomUniform::one()`, can be passed as config.
+impl<'a, T, D, X, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I, O> for Box<X>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+    X: DynInit<'a, T, D> + 'static,
+{
+    fn into_config(self) -> LinearConfig<'a, T, D, I, O> {
+        LinearConfig {
+            init: self,
+            ..Default::default()
+        }
+    }
+}
+
 pub struct Bias(pub bool);
 
 impl<'a, T, D, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I, O> for Bias
@@ -67,6 +94,19 @@ where
     }
 }
 
+impl<'a, T, D, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I, O> for BiasInit
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> LinearConfig<'a, T, D, I, O> {
+        LinearConfig {
+            bias_init: self,
+            ..Default::default()
+        }
+    }
+}
+
 pub struct L2<T>(pub T);
 
 impl<'a, T, D, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I, O> for L2<T>
@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+
+use custos::{number::Float, Alloc, CDatatype, GenericBlas, GraphReturn};
+use custos_math::{CudaTranspose, Matrix};
+
+use super::{forward, param_grads, Bias, BiasInit, DynInit, Glorot, L2Loss, L2Reg, Linear, L2};
+use crate::{GetParam, Layer, LayerShape, Param};
+
+/// A linear layer, whose input and output sizes are chosen at runtime.
+/// It can be converted from and into a [`Linear`] layer with matching dimensions.
+///
+/// # Example
+/// ```
+/// use gradients::prelude::*;
+///
+/// let device = CPU::new();
+///
+/// let hidden = 32;
+/// let dyn_linear = DynLinear::<f32>::new(&device, 4, hidden, ());
+/// assert_eq!(dyn_linear.dims(), (4, 32));
+///
+/// let linear: Linear<f32, 4, 32> = dyn_linear.try_into().ok().unwrap();
+/// assert!(linear.bias.is_some());
+/// ```
+pub struct DynLinear<'a, T> {
+    pub weights: Matrix<'a, T>,
+    pub bias: Option<Matrix<'a, T>>,
+    pub dweights: Option<Matrix<'a, T>>,
+    pub dbias: Option<Matrix<'a, T>>,
+    inputs: Option<Matrix<'a, T>>,
+    pub l2_reg: T,
+    pub l2_reg_loss: Option<&'a RefCell<T>>,
+}
+
+impl<'a, T: Copy + Float> DynLinear<'a, T> {
+    pub fn new<'b: 'a, D>(
+        device: &'b D,
+        inputs: usize,
+        outputs: usize,
+        args: impl IntoDynLinearConfig<'a, T, D>,
+    ) -> DynLinear<'a, T>
+    where
+        D: Alloc<T> + GraphReturn + 'a,
+    {
+        let config = args.into_config();
+        let with_bias = config.bias && config.bias_init.from_init();
+        let (weights, bias) = config.init.init_dyn(device, inputs, outputs, with_bias);
+        let bias = config
+            .bias
+            .then(|| config.bias_init.init(device, outputs, bias));
+
+        DynLinear {
+            weights,
+            bias,
+            dweights: None,
+            dbias: None,
+            inputs: None,
+            l2_reg: config.l2_reg,
+            l2_reg_loss: config.l2_reg_loss,
+        }
+    }
+
+    /// Returns the input and output size of the layer.
+    pub fn dims(&self) -> (usize, usize) {
+        self.weights.dims()
+    }
+
+    pub fn set_l2_reg_loss(&mut self, l2_reg_loss: &'a RefCell<T>) -> &mut Self {
+        self.l2_reg_loss = Some(l2_reg_loss);
+        self
+    }
+}
+
+impl<'a, T: Float + GenericBlas + CDatatype> DynLinear<'a, T> {
+    pub fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        self.inputs = Some(inputs.shallow_or_clone());
+        forward(
+            inputs,
+            &self.weights,
+            self.bias.as_ref(),
+            self.l2_reg,
+            self.l2_reg_loss,
+        )
+    }
+
+    pub fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T>
+    where
+        T: CudaTranspose,
+    {
+        let (dweights, dbias) = param_grads(
+            self.inputs.as_ref().unwrap(),
+            grad,
+            &self.weights,
+            self.bias.as_ref(),
+            self.l2_reg,
+        );
+        self.dweights = Some(dweights);
+        self.dbias = dbias;
+
+        grad.gemm(&self.weights.T())
+    }
+}
+
+impl<'a, T: Copy> GetParam<'a, T> for DynLinear<'a, T> {
+    fn params(&mut self) -> Option<Param<'a, T>> {
+        Some(Param::new(
+            self.weights.shallow(),
+            self.bias.as_ref().map(|bias| bias.shallow()),
+            self.dweights.as_ref().unwrap().shallow(),
+            self.dbias.as_ref().map(|dbias| dbias.shallow()),
+        ))
+    }
+}
+
+impl<'a, T> LayerShape for DynLinear<'a, T> {
+    fn input_size(&self) -> Option<usize> {
+        Some(self.weights.rows())
+    }
+
+    fn output_size(&self, _input_size: Option<usize>) -> Option<usize> {
+        Some(self.weights.cols())
+    }
+
+    fn param_count(&self) -> usize {
+        let (inputs, outputs) = self.weights.dims();
+        inputs * outputs + self.bias.as_ref().map_or(0, |_| outputs)
+    }
+}
+
+impl<'a, T> Layer<'a, T> for DynLinear<'a, T>
+where
+    T: Float + GenericBlas + CDatatype + CudaTranspose,
+{
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        DynLinear::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        DynLinear::backward(self, grad)
+    }
+}
+
+impl<'a, T, const I: usize, const O: usize> From<Linear<'a, T, I, O>> for DynLinear<'a, T> {
+    fn from(linear: Linear<'a, T, I, O>) -> Self {
+        DynLinear {
+            weights: linear.weights,
+            bias: linear.bias,
+            dweights: linear.dweights,
+            dbias: linear.dbias,
+            inputs: linear.inputs,
+            l2_reg: linear.l2_reg,
+            l2_reg_loss: linear.l2_reg_loss,
+        }
+    }
+}
+
+/// Fails if the dimensions of the layer are not `(I, O)`. The layer is returned in this case.
+impl<'a, T, const I: usize, const O: usize> TryFrom<DynLinear<'a, T>> for Linear<'a, T, I, O> {
+    type Error = DynLinear<'a, T>;
+
+    fn try_from(linear: DynLinear<'a, T>) -> Result<Self, Self::Error> {
+        if linear.weights.dims() != (I, O) {
+            return Err(linear);
+        }
+
+        Ok(Linear {
+            weights: linear.weights,
+            bias: linear.bias,
+            dweights: linear.dweights,
+            dbias: linear.dbias,
+            inputs: linear.inputs,
+            l2_reg: linear.l2_reg,
+            l2_reg_loss: linear.l2_reg_loss,
+        })
+    }
+}
+
+/// The runtime counterpart of [`LinearConfig`](super::LinearConfig).
+pub struct DynLinearConfig<'a, T, D> {
+    pub init: Box<dyn DynInit<'a, T, D>>,
+    pub bias: bool,
+    pub bias_init: BiasInit,
+    pub l2_reg: T,
+    pub l2_reg_loss: Option<&'a RefCell<T>>,
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> Default for DynLinearConfig<'a, T, D> {
+    fn default() -> Self {
+        Self {
+            init: Box::new(Glorot),
+            bias: true,
+            bias_init: BiasInit::default(),
+            l2_reg: T::default(),
+            l2_reg_loss: None,
+        }
+    }
+}
+
+pub trait IntoDynLinearConfig<'a, T, D: 'a> {
+    fn into_config(self) -> DynLinearConfig<'a, T, D>;
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for ()
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig::default()
+    }
+}
+
+impl<'a, T, D: 'a> IntoDynLinearConfig<'a, T, D> for DynLinearConfig<'a, T, D> {
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        self
+    }
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for Bias
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            bias: self.0,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for BiasInit
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            bias_init: self,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for L2<T>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            l2_reg: self.0,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for &'a L2Reg<T>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            l2_reg: self.l2,
+            l2_reg_loss: Some(&self.loss),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, T, D> IntoDynLinearConfig<'a, T, D> for L2Loss<'a, T>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            l2_reg_loss: Some(self.loss),
+            ..Default::default()
+        }
+    }
+}
+
+/// Every boxed initializer, e.g. `Glorot::new()` or `RandomUniform::one()`, can be passed as config.
+impl<'a, T, D, X> IntoDynLinearConfig<'a, T, D> for Box<X>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn + 'a,
+    X: DynInit<'a, T, D> + 'static,
+{
+    fn into_config(self) -> DynLinearConfig<'a, T, D> {
+        DynLinearConfig {
+            init: self,
+            ..Default::default()
+        }
+    }
+}
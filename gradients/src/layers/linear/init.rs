@@ -1,12 +1,85 @@
+mod bias;
+mod kaiming;
+mod normal;
+mod orthogonal;
+
 use custos::{number::Float, Alloc, GraphReturn};
 use custos_math::Matrix;
 
-use super::{LinearParams, IntoLinearConfig, LinearConfig};
+pub use bias::*;
+pub use kaiming::*;
+pub use normal::*;
+pub use orthogonal::*;
+
+use super::LinearParams;
+use crate::rng::{with_rng, Rng};
 
 pub trait Init<'a, T, D, const I: usize, const O: usize> {
     fn init(&self, device: &'a D, with_bias: bool) -> LinearParams<'a, T>;
 }
 
+/// Initializes the parameters of a layer, whose dimensions are only known at runtime.
+/// Every `DynInit` can be used as an [`Init`] as well.
+pub trait DynInit<'a, T, D> {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T>;
+}
+
+impl<'a, T, D, X, const I: usize, const O: usize> Init<'a, T, D, I, O> for X
+where
+    X: DynInit<'a, T, D> + ?Sized,
+{
+    fn init(&self, device: &'a D, with_bias: bool) -> LinearParams<'a, T> {
+        self.init_dyn(device, I, O, with_bias)
+    }
+}
+
+impl<'a, T, D, X: DynInit<'a, T, D> + ?Sized> DynInit<'a, T, D> for Box<X> {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        (**self).init_dyn(device, inputs, outputs, with_bias)
+    }
+}
+
+/// Samples every weight and the bias with `sample` on the host and writes them to the device afterwards.
+/// Unlike `Matrix::rand`, this works for every device and can be seeded.
+fn sample_params<'a, T, D>(
+    device: &'a D,
+    inputs: usize,
+    outputs: usize,
+    with_bias: bool,
+    mut sample: impl FnMut(&mut Rng) -> T,
+) -> LinearParams<'a, T>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn,
+{
+    with_rng(|rng| {
+        let values = (0..inputs * outputs)
+            .map(|_| sample(rng))
+            .collect::<Vec<T>>();
+        let weights = Matrix::from((device, (inputs, outputs), values));
+
+        let mut bias = None;
+        if with_bias {
+            let values = (0..outputs).map(|_| sample(rng)).collect::<Vec<T>>();
+            bias = Some(Matrix::from((device, (1, outputs), values)));
+        }
+
+        (weights, bias)
+    })
+}
+
 pub struct RandomUniform<T> {
     pub min: T,
     pub max: T,
@@ -27,35 +100,21 @@ impl<T> RandomUniform<T> {
     }
 }
 
-impl<'a, T, D, const I: usize, const O: usize> IntoLinearConfig<'a, T, D, I, O> for Box<RandomUniform<T>>
-where
-    T: Float + 'static,
-    D: Alloc<T> + GraphReturn + 'a
-{
-    fn into_config(self) -> LinearConfig<'a, T, D, I, O> {
-        LinearConfig { 
-            init: self,
-            ..Default::default()
-        }
-    }
-}
-
-
-impl<'a, T, D, const I: usize, const O: usize> Init<'a, T, D, I, O> for RandomUniform<T>
+impl<'a, T, D> DynInit<'a, T, D> for RandomUniform<T>
 where
     T: Float,
     D: Alloc<T> + GraphReturn,
 {
-    fn init(&self, device: &'a D, with_bias: bool) -> LinearParams<'a, T> {
-        let mut weights = Matrix::<T>::from((device, I, O));
-        weights.rand(self.min, self.max);
-
-        let mut bias = None;
-        if with_bias {
-            bias = Some(Matrix::<T>::from((device, 1, O)));
-        }
-
-        (weights, bias)
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            rng.uniform(self.min, self.max)
+        })
     }
 }
 
@@ -67,21 +126,18 @@ impl Glorot {
     }
 }
 
-impl<'a, T: Float, D: Alloc<T> + GraphReturn, const I: usize, const O: usize> Init<'a, T, D, I, O>
-    for Glorot
-{
-    fn init(&self, device: &'a D, with_bias: bool) -> LinearParams<'a, T> {
-        let mut weights = Matrix::<T>::from((device, I, O));
-
-        let glorot = (T::from_usize(6) / T::from_usize(I + O)).sqrt();
-
-        weights.rand(-glorot, glorot);
-
-        let mut bias = None;
-        if with_bias {
-            bias = Some(Matrix::<T>::from((device, 1, O)));
-        }
-
-        (weights, bias)
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for Glorot {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let glorot = (T::from_usize(6) / T::from_usize(inputs + outputs)).sqrt();
+
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            rng.uniform(-glorot, glorot)
+        })
     }
 }
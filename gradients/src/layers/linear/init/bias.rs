@@ -0,0 +1,66 @@
+use custos::{number::Float, Alloc, GraphReturn};
+use custos_math::Matrix;
+
+use crate::rng::rand_matrix;
+
+/// Initializes the bias of a linear layer. It is applied after the weights are initialized.
+///
+/// # Example
+/// ```
+/// use gradients::{linear::BiasInit, prelude::*};
+///
+/// let device = CPU::new();
+///
+/// let lin = Linear::<f32, 4, 8>::new(
+///     &device,
+///     LinearConfig {
+///         bias_init: BiasInit::Constant(0.01),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(lin.bias.unwrap().read(), vec![0.01; 8]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BiasInit {
+    /// Keeps the bias returned by the weight initializer.
+    /// The built-in initializers sample it from the same distribution as the weights, except [`Orthogonal`](super::Orthogonal), which returns zeros.
+    FromInit,
+    #[default]
+    Zeros,
+    /// Sets every value of the bias, e.g. a positive bias for ReLU networks.
+    Constant(f64),
+    /// Samples the bias from `U(min, max)`.
+    Uniform { min: f64, max: f64 },
+}
+
+impl BiasInit {
+    /// Returns true, if the weight initializer has to initialize the bias.
+    /// Otherwise, the bias is not sampled by the weight initializer at all.
+    pub fn from_init(&self) -> bool {
+        *self == BiasInit::FromInit
+    }
+
+    /// Returns the initialized bias with `outputs` values. `bias` is the bias, which the weight initializer returned.
+    /// For `FromInit`, a missing bias is initialized with zeros.
+    pub fn init<'a, T, D>(
+        &self,
+        device: &'a D,
+        outputs: usize,
+        bias: Option<Matrix<'a, T>>,
+    ) -> Matrix<'a, T>
+    where
+        T: Float,
+        D: Alloc<T> + GraphReturn,
+    {
+        match *self {
+            BiasInit::FromInit => bias.unwrap_or_else(|| Matrix::from((device, 1, outputs))),
+            BiasInit::Zeros => Matrix::from((device, 1, outputs)),
+            BiasInit::Constant(value) => {
+                Matrix::from((device, (1, outputs), vec![T::as_generic(value); outputs]))
+            }
+            BiasInit::Uniform { min, max } => {
+                rand_matrix(device, (1, outputs), T::as_generic(min), T::as_generic(max))
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use custos::{number::Float, Alloc, GraphReturn};
+
+use super::{sample_params, DynInit, LinearParams};
+
+/// Whether the variance of the weights is preserved in the forward or in the backward pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanMode {
+    /// Uses the number of inputs, which preserves the variance in the forward pass.
+    FanIn,
+    /// Uses the number of outputs, which preserves the variance in the backward pass.
+    FanOut,
+}
+
+impl FanMode {
+    fn fan(&self, inputs: usize, outputs: usize) -> usize {
+        match self {
+            FanMode::FanIn => inputs,
+            FanMode::FanOut => outputs,
+        }
+    }
+}
+
+/// The activation function following a layer. It determines the gain of the initialization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Nonlinearity {
+    Linear,
+    Sigmoid,
+    Tanh,
+    ReLU,
+    /// A leaky ReLU with the given negative slope.
+    LeakyReLU(f64),
+}
+
+impl Nonlinearity {
+    /// The recommended factor, which scales the standard deviation of the weights.
+    pub fn gain(&self) -> f64 {
+        match self {
+            Nonlinearity::Linear | Nonlinearity::Sigmoid => 1.,
+            Nonlinearity::Tanh => 5. / 3.,
+            Nonlinearity::ReLU => 2f64.sqrt(),
+            Nonlinearity::LeakyReLU(slope) => (2. / (1. + slope * slope)).sqrt(),
+        }
+    }
+}
+
+/// He / Kaiming initialization. The weights are sampled from `U(-bound, bound)` with `bound = gain * sqrt(3 / fan)`.
+///
+/// # Example
+/// ```
+/// use gradients::{linear::{FanMode, KaimingUniform, Nonlinearity}, prelude::*};
+///
+/// let device = CPU::new();
+///
+/// let lin = Linear::<f32, 128, 64>::new(&device, KaimingUniform::relu());
+/// let lin = Linear::<f32, 128, 64>::new(
+///     &device,
+///     KaimingUniform::new(FanMode::FanOut, Nonlinearity::LeakyReLU(0.01)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KaimingUniform {
+    pub mode: FanMode,
+    pub nonlinearity: Nonlinearity,
+}
+
+impl KaimingUniform {
+    pub fn new(mode: FanMode, nonlinearity: Nonlinearity) -> Box<KaimingUniform> {
+        Box::new(KaimingUniform { mode, nonlinearity })
+    }
+
+    /// Uses the number of inputs and the gain of ReLU.
+    pub fn relu() -> Box<KaimingUniform> {
+        KaimingUniform::new(FanMode::FanIn, Nonlinearity::ReLU)
+    }
+
+    pub fn bound(&self, inputs: usize, outputs: usize) -> f64 {
+        self.nonlinearity.gain() * (3. / self.mode.fan(inputs, outputs) as f64).sqrt()
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for KaimingUniform {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let bound = T::as_generic(self.bound(inputs, outputs));
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            rng.uniform(-bound, bound)
+        })
+    }
+}
+
+/// He / Kaiming initialization. The weights are sampled from `N(0, std²)` with `std = gain / sqrt(fan)`.
+///
+/// The samples are drawn on the host, hence this works for every device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KaimingNormal {
+    pub mode: FanMode,
+    pub nonlinearity: Nonlinearity,
+}
+
+impl KaimingNormal {
+    pub fn new(mode: FanMode, nonlinearity: Nonlinearity) -> Box<KaimingNormal> {
+        Box::new(KaimingNormal { mode, nonlinearity })
+    }
+
+    /// Uses the number of inputs and the gain of ReLU.
+    pub fn relu() -> Box<KaimingNormal> {
+        KaimingNormal::new(FanMode::FanIn, Nonlinearity::ReLU)
+    }
+
+    pub fn std(&self, inputs: usize, outputs: usize) -> f64 {
+        self.nonlinearity.gain() / (self.mode.fan(inputs, outputs) as f64).sqrt()
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for KaimingNormal {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let std = self.std(inputs, outputs);
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            T::as_generic(rng.normal(0., std))
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use custos::{number::Float, Alloc, GraphReturn};
+
+use super::{sample_params, DynInit, LinearParams};
+
+/// Glorot / Xavier initialization. The weights are sampled from `N(0, std²)` with `std = gain * sqrt(2 / (inputs + outputs))`.
+///
+/// # Example
+/// ```
+/// use gradients::{linear::XavierNormal, prelude::*};
+///
+/// let device = CPU::new();
+/// let lin = Linear::<f32, 64, 32>::new(&device, XavierNormal::new());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XavierNormal {
+    pub gain: f64,
+}
+
+impl XavierNormal {
+    pub fn new() -> Box<XavierNormal> {
+        XavierNormal::with_gain(1.)
+    }
+
+    pub fn with_gain(gain: f64) -> Box<XavierNormal> {
+        Box::new(XavierNormal { gain })
+    }
+
+    pub fn std(&self, inputs: usize, outputs: usize) -> f64 {
+        self.gain * (2. / (inputs + outputs) as f64).sqrt()
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for XavierNormal {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let std = self.std(inputs, outputs);
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            T::as_generic(rng.normal(0., std))
+        })
+    }
+}
+
+/// LeCun initialization, e.g. for SELU networks. The weights are sampled from `N(0, 1 / inputs)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeCunNormal;
+
+impl LeCunNormal {
+    pub fn new() -> Box<LeCunNormal> {
+        Box::new(LeCunNormal)
+    }
+
+    pub fn std(&self, inputs: usize) -> f64 {
+        (1. / inputs as f64).sqrt()
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for LeCunNormal {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let std = self.std(inputs);
+        sample_params(device, inputs, outputs, with_bias, |rng| {
+            T::as_generic(rng.normal(0., std))
+        })
+    }
+}
+
+/// Samples the weights from `N(mean, std²)`.
+/// Values, which are more than two standard deviations away from the mean, are sampled again.
+///
+/// # Example
+/// ```
+/// use gradients::{linear::TruncatedNormal, prelude::*};
+///
+/// let device = CPU::new();
+/// let lin = Linear::<f32, 64, 32>::new(&device, TruncatedNormal::new(0.02));
+///
+/// assert!(lin.weights.read().iter().all(|w| w.abs() <= 0.04));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TruncatedNormal {
+    pub mean: f64,
+    pub std: f64,
+}
+
+impl TruncatedNormal {
+    /// A truncated normal distribution with a mean of zero.
+    ///
+    /// # Panics
+    /// If `std` is negative, infinite or NaN.
+    pub fn new(std: f64) -> Box<TruncatedNormal> {
+        TruncatedNormal::with_mean(0., std)
+    }
+
+    /// # Panics
+    /// If `mean` is not finite or `std` is negative, infinite or NaN.
+    pub fn with_mean(mean: f64, std: f64) -> Box<TruncatedNormal> {
+        let init = TruncatedNormal { mean, std };
+        init.validate();
+        Box::new(init)
+    }
+
+    /// The rejection sampling never finishes for these values, hence they are rejected up front.
+    fn validate(&self) {
+        assert!(
+            self.mean.is_finite(),
+            "The mean must be finite, but it is {}.",
+            self.mean
+        );
+        assert!(
+            self.std >= 0. && self.std.is_finite(),
+            "The standard deviation must be finite and non-negative, but it is {}.",
+            self.std
+        );
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for TruncatedNormal {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        // the fields are public, so they could have been set without the constructor
+        self.validate();
+        sample_params(device, inputs, outputs, with_bias, |rng| loop {
+            let value = rng.normal(self.mean, self.std);
+            if (value - self.mean).abs() <= 2. * self.std {
+                break T::as_generic(value);
+            }
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use custos::{number::Float, Alloc, GraphReturn};
+use custos_math::Matrix;
+
+use super::{DynInit, LinearParams};
+use crate::rng::with_rng;
+
+/// Initializes the weights with a (semi-)orthogonal matrix, which is scaled by `gain`.
+/// If there are more inputs than outputs, the columns are orthonormal, otherwise the rows.
+///
+/// The matrix is computed on the host by orthonormalizing a Gaussian matrix with the Gram-Schmidt process.
+///
+/// # Example
+/// ```
+/// use gradients::{linear::{Nonlinearity, Orthogonal}, prelude::*};
+///
+/// let device = CPU::new();
+///
+/// let lin = Linear::<f32, 64, 64>::new(&device, Orthogonal::new());
+/// let lin = Linear::<f32, 64, 32>::new(&device, Orthogonal::with_gain(Nonlinearity::ReLU.gain()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orthogonal {
+    pub gain: f64,
+}
+
+impl Orthogonal {
+    pub fn new() -> Box<Orthogonal> {
+        Orthogonal::with_gain(1.)
+    }
+
+    pub fn with_gain(gain: f64) -> Box<Orthogonal> {
+        Box::new(Orthogonal { gain })
+    }
+}
+
+fn dot(lhs: &[f64], rhs: &[f64]) -> f64 {
+    lhs.iter().zip(rhs).map(|(lhs, rhs)| lhs * rhs).sum()
+}
+
+/// Orthonormalizes the vectors with the modified Gram-Schmidt process.
+/// There must not be more vectors than every vector has values.
+fn gram_schmidt(vectors: &mut [Vec<f64>]) {
+    for idx in 0..vectors.len() {
+        let (prev, rest) = vectors.split_at_mut(idx);
+        let vector = &mut rest[0];
+
+        for prev in prev.iter() {
+            let projection = dot(vector, prev);
+            vector
+                .iter_mut()
+                .zip(prev)
+                .for_each(|(value, prev)| *value -= projection * prev);
+        }
+
+        let norm = dot(vector, vector).sqrt();
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+}
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for Orthogonal {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> LinearParams<'a, T> {
+        let (len, count) = (inputs.max(outputs), inputs.min(outputs));
+
+        let mut vectors = with_rng(|rng| {
+            (0..count)
+                .map(|_| (0..len).map(|_| rng.normal(0., 1.)).collect::<Vec<f64>>())
+                .collect::<Vec<_>>()
+        });
+        gram_schmidt(&mut vectors);
+
+        let mut values = vec![T::default(); inputs * outputs];
+        for row in 0..inputs {
+            for col in 0..outputs {
+                // the vectors are either the columns or the rows of the weights
+                let value = if inputs >= outputs {
+                    vectors[col][row]
+                } else {
+                    vectors[row][col]
+                };
+                values[row * outputs + col] = T::as_generic(self.gain * value);
+            }
+        }
+        let weights = Matrix::from((device, (inputs, outputs), values));
+
+        let mut bias = None;
+        if with_bias {
+            bias = Some(Matrix::<T>::from((device, 1, outputs)));
+        }
+
+        (weights, bias)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::Layer;
+use custos::{get_device, number::Float, CDatatype, CacheBuf, CPU};
+use custos_math::Matrix;
+use gradients_derive::NoParams;
+
+#[cfg(feature = "opencl")]
+use custos_math::{cl_to_cpu_lr, cl_to_cpu_s};
+
+/// Computes `x - log(sum(exp(x)))` for every row.
+/// The log-sum-exp trick (subtracting the row maximum first) keeps this stable for large logits.
+#[derive(NoParams)]
+pub struct LogSoftmax<'a, T> {
+    activated: Option<Matrix<'a, T>>,
+}
+
+impl<'a, T: Float + CDatatype> LogSoftmax<'a, T> {
+    pub fn new() -> Self {
+        LogSoftmax { activated: None }
+    }
+
+    pub fn forward(&mut self, x: &Matrix<'a, T>) -> Matrix<'a, T> {
+        let activated = x.log_softmax();
+        self.activated = Some(activated.shallow_or_clone());
+        activated
+    }
+
+    pub fn backward(&self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        grad.log_softmax_grad(self.activated.as_ref().unwrap())
+    }
+}
+
+impl<'a, T> Default for LogSoftmax<'a, T> {
+    fn default() -> Self {
+        Self {
+            activated: Default::default(),
+        }
+    }
+}
+
+impl<'a, T: Float + CDatatype> Layer<'a, T> for LogSoftmax<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        LogSoftmax::forward(self, inputs)
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        LogSoftmax::backward(self, grad)
+    }
+}
+
+/// Computes `log(sum(exp(values)))` without overflowing for large values.
+pub(crate) fn log_sum_exp<T: Float>(values: &[T]) -> T {
+    let max = values.iter().fold(
+        values[0],
+        |max, &value| if value > max { value } else { max },
+    );
+
+    let sum = values
+        .iter()
+        .fold(T::zero(), |sum, &value| sum + (value - max).exp());
+
+    max + sum.ln()
+}
+
+pub trait LogSoftmaxMat<'a, T> {
+    fn log_softmax(&self) -> Matrix<'a, T>;
+    fn log_softmax_grad(&self, activated: &Matrix<'a, T>) -> Matrix<'a, T>;
+}
+
+impl<'a, T: Float + CDatatype> LogSoftmaxMat<'a, T> for Matrix<'a, T> {
+    fn log_softmax(&self) -> Matrix<'a, T> {
+        get_device!(self.device, LogSoftmaxOp<T>).log_softmax(self)
+    }
+
+    fn log_softmax_grad(&self, activated: &Matrix<'a, T>) -> Matrix<'a, T> {
+        get_device!(self.device, LogSoftmaxOp<T>).log_softmax_grad(activated, self)
+    }
+}
+
+pub trait LogSoftmaxOp<T> {
+    fn log_softmax(&self, x: &Matrix<'_, T>) -> Matrix<'_, T>;
+    fn log_softmax_grad(&self, activated: &Matrix<'_, T>, grad: &Matrix<'_, T>) -> Matrix<'_, T>;
+}
+
+impl<T: Float> LogSoftmaxOp<T> for CPU {
+    fn log_softmax(&self, x: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let cols = x.cols();
+        let mut out = self.cached(x.size());
+
+        for (row, values) in x.chunks(cols).enumerate() {
+            let log_sum_exp = log_sum_exp(values);
+
+            for (idx, value) in values.iter().enumerate() {
+                out[row * cols + idx] = *value - log_sum_exp;
+            }
+        }
+
+        (out, x.dims()).into()
+    }
+
+    fn log_softmax_grad(&self, activated: &Matrix<'_, T>, grad: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let cols = grad.cols();
+        let mut out = self.cached(grad.size());
+
+        for (row, (activated, grad)) in activated.chunks(cols).zip(grad.chunks(cols)).enumerate() {
+            let grad_sum = grad.iter().fold(T::zero(), |sum, &value| sum + value);
+
+            for idx in 0..cols {
+                out[row * cols + idx] = grad[idx] - activated[idx].exp() * grad_sum;
+            }
+        }
+
+        (out, grad.dims()).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: Float + CDatatype> LogSoftmaxOp<T> for custos::CLDevice {
+    fn log_softmax(&self, x: &Matrix<'_, T>) -> Matrix<'_, T> {
+        cl_to_cpu_s(self, x, |device, x| device.log_softmax(x))
+    }
+
+    fn log_softmax_grad(&self, activated: &Matrix<'_, T>, grad: &Matrix<'_, T>) -> Matrix<'_, T> {
+        cl_to_cpu_lr(self, activated, grad, |device, activated, grad| {
+            device.log_softmax_grad(activated, grad)
+        })
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: Float + CDatatype> LogSoftmaxOp<T> for custos::CudaDevice {
+    fn log_softmax(&self, x: &Matrix<'_, T>) -> Matrix<'_, T> {
+        custos_math::cu_to_cpu_s(self, x, |device, x| device.log_softmax(x))
+    }
+
+    fn log_softmax_grad(&self, activated: &Matrix<'_, T>, grad: &Matrix<'_, T>) -> Matrix<'_, T> {
+        custos_math::cu_to_cpu_lr(self, activated, grad, |device, activated, grad| {
+            device.log_softmax_grad(activated, grad)
+        })
+    }
+}
@@ -1,6 +1,8 @@
 mod activations;
 mod conv2d;
 pub mod linear;
+mod log_softmax;
 
 pub use activations::*;
 pub use conv2d::*;
+pub use log_softmax::*;
@@ -1,9 +1,17 @@
+extern crate self as gradients;
+
 mod accuracy;
 //mod batch;
 mod layers;
+mod loss;
+mod memory;
 mod ml;
 mod onehot;
 mod opt;
+mod rng;
+mod sequential;
+mod shape;
+mod summary;
 
 //exports of dependencies
 use custos::number::Float;
@@ -17,9 +25,15 @@ pub use gradients_derive::*;
 pub use accuracy::*;
 //pub use batch::*;
 pub use layers::*;
+pub use loss::*;
+pub use memory::*;
 pub use ml::*;
 pub use onehot::*;
 pub use opt::*;
+pub use rng::seed;
+pub use sequential::*;
+pub use shape::*;
+pub use summary::*;
 
 pub trait GetParam<'a, T> {
     fn params(&mut self) -> Option<Param<'a, T>> {
@@ -27,6 +41,14 @@ pub trait GetParam<'a, T> {
     }
 }
 
+/// A layer of a neural network.
+/// This trait is object safe, hence layers can be stored as `Box<dyn Layer<'a, T>>`, e.g. in a [`Sequential`].
+/// The shape of the layer is used to estimate the memory of a [`Sequential`] network.
+pub trait Layer<'a, T>: GetParam<'a, T> + LayerShape {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T>;
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T>;
+}
+
 pub trait WithDevice<'a, T> {
     fn with<'b: 'a, D: Alloc<T> + GraphReturn>(_device: &'b D) -> Self
     where
@@ -40,7 +62,7 @@ pub struct Param<'a, T> {
     pub weights: Matrix<'a, T>,
     pub bias: Option<Matrix<'a, T>>,
     pub dweights: Matrix<'a, T>,
-    pub dbias: Matrix<'a, T>,
+    pub dbias: Option<Matrix<'a, T>>,
 }
 
 impl<'a, T> Param<'a, T> {
@@ -48,7 +70,7 @@ impl<'a, T> Param<'a, T> {
         weights: Matrix<'a, T>,
         bias: Option<Matrix<'a, T>>,
         dweights: Matrix<'a, T>,
-        dbias: Matrix<'a, T>,
+        dbias: Option<Matrix<'a, T>>,
     ) -> Param<'a, T> {
         Param {
             weights,
@@ -63,6 +85,29 @@ pub trait NeuralNetwork<'a, T> {
     fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T>;
     fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T>;
     fn params(&mut self) -> Vec<Param<'a, T>>;
+
+    /// Returns an overview of the layers of the network.
+    /// `#[derive(NeuralNetwork)]` lists every field, otherwise the summary is empty.
+    fn summary_table(&self) -> Summary {
+        Summary::default()
+    }
+
+    /// Prints a table of the layers of the network, their input and output shapes and their number of parameters.
+    fn summary(&self) {
+        println!("{}", self.summary_table());
+    }
+
+    /// The shapes of the layers in the order they are executed.
+    /// `#[derive(NeuralNetwork)]` lists every field, otherwise no layers are returned.
+    fn layer_shapes(&self) -> Vec<&dyn LayerShape> {
+        Vec::new()
+    }
+
+    /// Estimates the memory, which is needed to train the network with `optimizer` on batches of `batch_size` samples.
+    /// The estimate is computed from the shapes of the layers, hence no forward or backward pass is needed.
+    fn memory_footprint(&self, optimizer: &dyn OptimizerState, batch_size: usize) -> MemoryFootprint {
+        MemoryFootprint::new::<T>(&self.layer_shapes(), optimizer, batch_size)
+    }
 }
 
 pub fn create_sine<D: Alloc<f32> + GraphReturn>(
@@ -102,10 +147,22 @@ pub mod prelude {
     pub use crate::{
         correct_classes, network, nn::*, range, Adam, Matrix, OneHotMat,
         PolynomialReg, ReLU, Softmax, Tanh, CPU, SGD, WithDevice, linear::*,
-        OnehotOp, LinearReg
+        OnehotOp, LinearReg, LogSoftmax, nll, nll_grad, softmax_cross_entropy,
+        Loss, Reduction, MSE, CCE, Layer, Sequential, NeuralNetwork
     };
     pub use purpur::*;
 
     #[cfg(feature = "opencl")]
     pub use crate::CLDevice;
 }
+
+/// The items, which the code generated by `#[derive(NeuralNetwork)]` used to import into the module of the network.
+/// They are still glob imported for existing code, which relies on them.
+/// Unlike a plain `use`, several glob imports of the same items don't conflict.
+#[doc(hidden)]
+pub mod derive_prelude {
+    pub use crate::{
+        number::{Float, Number},
+        Alloc, GetParam, GraphReturn, Matrix, NeuralNetwork, Param, WithDevice,
+    };
+}
@@ -0,0 +1,97 @@
+use custos::{number::Float, Alloc, CDatatype, GraphReturn};
+use custos_math::Matrix;
+
+use super::{Loss, Reduction};
+
+/// Binary cross-entropy on raw logits, averaged over all elements.
+/// `targets` must be in {0, 1}. Returns the loss and the gradient with respect to the logits.
+///
+/// See [`BCEWithLogits`] for `pos_weight`.
+pub fn bce_with_logits<'a, T: Float + CDatatype, D: Alloc<T> + GraphReturn>(
+    device: &'a D,
+    logits: &Matrix<T>,
+    targets: &Matrix<T>,
+    pos_weight: Option<T>,
+) -> (T, Matrix<'a, T>) {
+    let bce = BCEWithLogits {
+        pos_weight,
+        ..Default::default()
+    };
+    (
+        bce.loss(device, logits, targets).read()[0],
+        bce.grad(device, logits, targets),
+    )
+}
+
+/// Numerically stable `1 / (1 + exp(-x))`.
+pub(crate) fn sigmoid<T: Float>(x: T) -> T {
+    if x >= T::zero() {
+        T::one() / (T::one() + x.neg().exp())
+    } else {
+        let exp = x.exp();
+        exp / (T::one() + exp)
+    }
+}
+
+/// Numerically stable `log(sigmoid(x))`.
+pub(crate) fn log_sigmoid<T: Float>(x: T) -> T {
+    if x >= T::zero() {
+        (T::one() + x.neg().exp()).ln().neg()
+    } else {
+        x - (T::one() + x.exp()).ln()
+    }
+}
+
+/// Binary cross-entropy on raw logits for binary and multi-label tasks.
+/// The loss of a sample is the mean over its columns.
+///
+/// `pos_weight` scales the loss of the positive class, e.g. `negatives / positives` for imbalanced data.
+#[derive(Debug, Clone, Default)]
+pub struct BCEWithLogits<T> {
+    pub pos_weight: Option<T>,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for BCEWithLogits<T> {
+    fn sample_losses(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let pos_weight = self.pos_weight.unwrap_or(T::one());
+
+        logits
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(logits, targets)| {
+                logits
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (logit, target)| {
+                        sum - pos_weight * *target * log_sigmoid(*logit)
+                            - (T::one() - *target) * log_sigmoid(logit.neg())
+                    })
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let pos_weight = self.pos_weight.unwrap_or(T::one());
+
+        logits
+            .iter()
+            .zip(targets)
+            .map(|(logit, target)| {
+                let sigmoid = sigmoid(*logit);
+                (sigmoid * (T::one() - *target) - pos_weight * *target * (T::one() - sigmoid))
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
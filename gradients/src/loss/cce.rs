@@ -0,0 +1,77 @@
+use custos::{number::Float, CDatatype};
+
+use super::{class_targets, Loss, Reduction};
+
+/// Categorical cross-entropy on probabilities, e.g. the output of a [`Softmax`](crate::Softmax) layer.
+/// The predictions are clipped to [1e-7, 1 - 1e-7], like `cce` and `cce_grad` do.
+///
+/// The targets are smoothed to `(1 - label_smoothing) * targets + label_smoothing / classes`
+/// and then multiplied with the `class_weights`, if given.
+#[derive(Debug, Clone, Default)]
+pub struct CCE<T> {
+    pub class_weights: Option<Vec<T>>,
+    pub label_smoothing: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+/// Clips a probability to [1e-7, 1 - 1e-7].
+pub(crate) fn clip_prob<T: Float>(pred: T) -> T {
+    let min = T::as_generic(1e-7);
+    let max = T::one() - min;
+
+    if pred < min {
+        min
+    } else if pred > max {
+        max
+    } else {
+        pred
+    }
+}
+
+impl<T: Float> CCE<T> {
+    fn class_targets(&self, targets: &[T], cols: usize) -> Vec<T> {
+        class_targets(
+            targets,
+            cols,
+            false,
+            self.class_weights.as_deref(),
+            self.label_smoothing,
+        )
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for CCE<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let targets = self.class_targets(targets, cols);
+
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        sum - *target * clip_prob(*pred).ln()
+                    })
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .iter()
+            .zip(self.class_targets(targets, cols))
+            .map(|(pred, target)| (target / clip_prob(*pred)).neg())
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
@@ -0,0 +1,150 @@
+use custos::{number::Float, CDatatype};
+
+use super::{Loss, Reduction};
+
+/// Keeps probabilities away from 0 before they are passed to `ln` or used as a divisor.
+fn clip_min<T: Float>(prob: T) -> T {
+    let min = T::as_generic(1e-7);
+    if prob < min {
+        min
+    } else {
+        prob
+    }
+}
+
+/// `x * log(x / y)`, which is 0 for `x = 0`.
+fn x_log_x_div_y<T: Float>(x: T, y: T) -> T {
+    if x <= T::zero() {
+        return T::zero();
+    }
+    x * (x / clip_min(y)).ln()
+}
+
+/// Kullback-Leibler divergence `KL(targets || preds) = sum(targets * log(targets / preds))` of every sample (row).
+///
+/// If `log_input` is set, the predictions are log-probabilities (e.g. the output of [`LogSoftmax`](crate::LogSoftmax)),
+/// otherwise probabilities. The targets are always probabilities.
+#[derive(Debug, Clone, Default)]
+pub struct KLDivergence<T> {
+    pub log_input: bool,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for KLDivergence<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        if *target <= T::zero() {
+                            return sum;
+                        }
+
+                        if self.log_input {
+                            sum + *target * (target.ln() - *pred)
+                        } else {
+                            sum + x_log_x_div_y(*target, *pred)
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], _cols: usize) -> Vec<T> {
+        preds
+            .iter()
+            .zip(targets)
+            .map(|(pred, target)| {
+                if self.log_input {
+                    target.neg()
+                } else {
+                    (*target / clip_min(*pred)).neg()
+                }
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Jensen-Shannon divergence `(KL(targets || m) + KL(preds || m)) / 2` with `m = (targets + preds) / 2` of every sample (row).
+/// It is symmetric and bounded by `log(2)`.
+///
+/// `log_input` has the same meaning as in [`KLDivergence`].
+#[derive(Debug, Clone, Default)]
+pub struct JSDivergence<T> {
+    pub log_input: bool,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> JSDivergence<T> {
+    fn probs(&self, preds: &[T]) -> Vec<T> {
+        if self.log_input {
+            preds.iter().map(|pred| pred.exp()).collect()
+        } else {
+            preds.to_vec()
+        }
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for JSDivergence<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let half = T::one() / T::two();
+        let preds = self.probs(preds);
+
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        let m = (*pred + *target) * half;
+                        sum + (x_log_x_div_y(*target, m) + x_log_x_div_y(*pred, m)) * half
+                    })
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], _cols: usize) -> Vec<T> {
+        let half = T::one() / T::two();
+
+        self.probs(preds)
+            .into_iter()
+            .zip(targets)
+            .map(|(pred, target)| {
+                let m = (pred + *target) * half;
+
+                // djs / dpred = log(pred / m) / 2
+                let grad = (clip_min(pred) / clip_min(m)).ln() * half;
+
+                if self.log_input {
+                    grad * pred
+                } else {
+                    grad
+                }
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
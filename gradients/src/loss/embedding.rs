@@ -0,0 +1,131 @@
+use custos::{number::Float, Alloc, CDatatype, GraphReturn};
+use custos_math::Matrix;
+
+/// Returns the euclidean distance of every row of `lhs` and `rhs`.
+fn distances<T: Float>(lhs: &[T], rhs: &[T], cols: usize) -> Vec<T> {
+    lhs.chunks(cols)
+        .zip(rhs.chunks(cols))
+        .map(|(lhs, rhs)| squared_distance(lhs, rhs).sqrt())
+        .collect()
+}
+
+fn squared_distance<T: Float>(lhs: &[T], rhs: &[T]) -> T {
+    lhs.iter().zip(rhs).fold(T::zero(), |sum, (lhs, rhs)| {
+        sum + (*lhs - *rhs) * (*lhs - *rhs)
+    })
+}
+
+/// Contrastive loss of embedding pairs, averaged over the pairs:
+/// `labels * d^2 / 2 + (1 - labels) * max(0, margin - d)^2 / 2`, where `d` is the euclidean distance of a pair (row of `lhs` and `rhs`).
+///
+/// `labels` is a (pairs x 1) matrix with 1 for similar and 0 for dissimilar pairs.
+/// Returns the loss and the gradients with respect to `lhs` and `rhs`.
+pub fn contrastive<'a, T: Float + CDatatype, D: Alloc<T> + GraphReturn>(
+    device: &'a D,
+    lhs: &Matrix<T>,
+    rhs: &Matrix<T>,
+    labels: &Matrix<T>,
+    margin: T,
+) -> (T, Matrix<'a, T>, Matrix<'a, T>) {
+    assert_eq!(lhs.dims(), rhs.dims());
+    assert!(labels.rows() == lhs.rows() && labels.cols() == 1);
+
+    let dims = lhs.dims();
+    let cols = lhs.cols();
+    let samples = T::from_usize(lhs.rows());
+    let half = T::one() / T::two();
+
+    let (lhs, rhs, labels) = (lhs.read(), rhs.read(), labels.read());
+
+    let mut loss = T::zero();
+    let mut dlhs = Vec::with_capacity(lhs.len());
+
+    for (((lhs, rhs), label), distance) in lhs
+        .chunks(cols)
+        .zip(rhs.chunks(cols))
+        .zip(&labels)
+        .zip(distances(&lhs, &rhs, cols))
+    {
+        let violation = if margin > distance {
+            margin - distance
+        } else {
+            T::zero()
+        };
+
+        loss += (*label * distance * distance + (T::one() - *label) * violation * violation) * half;
+
+        // the dissimilar term is not differentiable for identical embeddings, hence 0 is used
+        let dissimilar_scale = if distance > T::zero() {
+            (T::one() - *label) * violation / distance
+        } else {
+            T::zero()
+        };
+
+        for (lhs, rhs) in lhs.iter().zip(rhs) {
+            dlhs.push((*lhs - *rhs) * (*label - dissimilar_scale) / samples);
+        }
+    }
+
+    let drhs = dlhs.iter().map(|grad| grad.neg()).collect::<Vec<T>>();
+
+    (
+        loss / samples,
+        Matrix::from((device, dims, dlhs)),
+        Matrix::from((device, dims, drhs)),
+    )
+}
+
+/// Triplet loss `max(0, |anchor - positive|^2 - |anchor - negative|^2 + margin)` on squared euclidean distances, averaged over the triplets (rows).
+///
+/// Returns the loss and the gradients with respect to `anchor`, `positive` and `negative`.
+/// Therefore, the same network can be run on the three batches and trained with each of the gradients.
+pub fn triplet<'a, T: Float + CDatatype, D: Alloc<T> + GraphReturn>(
+    device: &'a D,
+    anchor: &Matrix<T>,
+    positive: &Matrix<T>,
+    negative: &Matrix<T>,
+    margin: T,
+) -> (T, Matrix<'a, T>, Matrix<'a, T>, Matrix<'a, T>) {
+    assert!(anchor.dims() == positive.dims() && anchor.dims() == negative.dims());
+
+    let dims = anchor.dims();
+    let cols = anchor.cols();
+    let samples = T::from_usize(anchor.rows());
+
+    let (anchor, positive, negative) = (anchor.read(), positive.read(), negative.read());
+
+    let mut loss = T::zero();
+    let mut danchor = vec![T::zero(); anchor.len()];
+    let mut dpositive = vec![T::zero(); anchor.len()];
+    let mut dnegative = vec![T::zero(); anchor.len()];
+
+    for (row, ((anchor, positive), negative)) in anchor
+        .chunks(cols)
+        .zip(positive.chunks(cols))
+        .zip(negative.chunks(cols))
+        .enumerate()
+    {
+        let violation =
+            squared_distance(anchor, positive) - squared_distance(anchor, negative) + margin;
+
+        if violation <= T::zero() {
+            continue;
+        }
+        loss += violation;
+
+        let scale = T::two() / samples;
+        for col in 0..cols {
+            let idx = row * cols + col;
+            danchor[idx] = (negative[col] - positive[col]) * scale;
+            dpositive[idx] = (positive[col] - anchor[col]) * scale;
+            dnegative[idx] = (anchor[col] - negative[col]) * scale;
+        }
+    }
+
+    (
+        loss / samples,
+        Matrix::from((device, dims, danchor)),
+        Matrix::from((device, dims, dpositive)),
+        Matrix::from((device, dims, dnegative)),
+    )
+}
@@ -0,0 +1,203 @@
+use custos::{number::Float, CDatatype};
+
+use super::{
+    bce::{log_sigmoid, sigmoid},
+    Loss, Reduction,
+};
+use crate::layers::log_sum_exp;
+
+/// Binary focal loss on raw logits: `-alpha_t * (1 - p_t)^gamma * log(p_t)`.
+/// Down-weights well classified examples, so that training focuses on the hard ones.
+///
+/// `alpha` weights the positive class (and `1 - alpha` the negative class). Without `alpha`, both classes are weighted with 1.
+/// Like [`BCEWithLogits`](crate::BCEWithLogits), the loss of a sample is the mean over its columns.
+#[derive(Debug, Clone, Default)]
+pub struct BinaryFocal<T> {
+    pub gamma: T,
+    pub alpha: Option<T>,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> BinaryFocal<T> {
+    pub fn new(gamma: T, alpha: Option<T>) -> Self {
+        BinaryFocal {
+            gamma,
+            alpha,
+            reduction: Reduction::Mean,
+            weights: None,
+        }
+    }
+
+    /// Returns the alpha of the positive and the negative class.
+    fn alphas(&self) -> (T, T) {
+        match self.alpha {
+            Some(alpha) => (alpha, T::one() - alpha),
+            None => (T::one(), T::one()),
+        }
+    }
+}
+
+/// The loss `-alpha * (1 - sigmoid(z))^gamma * log(sigmoid(z))` of a single logit `z`.
+fn focal_term<T: Float>(z: T, alpha: T, gamma: T) -> T {
+    (alpha * sigmoid(z.neg()).powf(gamma) * log_sigmoid(z)).neg()
+}
+
+/// The derivative of [`focal_term`] with respect to `z`.
+fn focal_term_grad<T: Float>(z: T, alpha: T, gamma: T) -> T {
+    let p = sigmoid(z);
+    let one_minus_p = sigmoid(z.neg());
+
+    alpha
+        * (gamma * one_minus_p.powf(gamma) * p * log_sigmoid(z)
+            - one_minus_p.powf(gamma + T::one()))
+}
+
+impl<T: Float + CDatatype> Loss<T> for BinaryFocal<T> {
+    fn sample_losses(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let (pos_alpha, neg_alpha) = self.alphas();
+
+        logits
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(logits, targets)| {
+                logits
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (logit, target)| {
+                        sum + *target * focal_term(*logit, pos_alpha, self.gamma)
+                            + (T::one() - *target) * focal_term(logit.neg(), neg_alpha, self.gamma)
+                    })
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let (pos_alpha, neg_alpha) = self.alphas();
+
+        logits
+            .iter()
+            .zip(targets)
+            .map(|(logit, target)| {
+                (*target * focal_term_grad(*logit, pos_alpha, self.gamma)
+                    - (T::one() - *target) * focal_term_grad(logit.neg(), neg_alpha, self.gamma))
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Multi-class focal loss on logits: `-sum(alpha_c * targets_c * (1 - p_c)^gamma * log(p_c))` with `p = softmax(logits)`.
+/// The targets are one-hot encoded.
+///
+/// `alpha` weights every class. Without `alpha`, all classes are weighted with 1.
+#[derive(Debug, Clone, Default)]
+pub struct CategoricalFocal<T> {
+    pub gamma: T,
+    pub alpha: Option<Vec<T>>,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> CategoricalFocal<T> {
+    pub fn new(gamma: T, alpha: Option<Vec<T>>) -> Self {
+        CategoricalFocal {
+            gamma,
+            alpha,
+            reduction: Reduction::Mean,
+            weights: None,
+        }
+    }
+
+    fn check_alpha(&self, cols: usize) {
+        if let Some(alpha) = &self.alpha {
+            assert_eq!(
+                alpha.len(),
+                cols,
+                "The number of alpha values must match the number of classes."
+            );
+        }
+    }
+
+    fn alpha(&self, class: usize) -> T {
+        self.alpha
+            .as_ref()
+            .map(|alpha| alpha[class])
+            .unwrap_or(T::one())
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for CategoricalFocal<T> {
+    fn sample_losses(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        self.check_alpha(cols);
+
+        logits
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(logits, targets)| {
+                let log_sum_exp = log_sum_exp(logits);
+
+                logits.iter().zip(targets).enumerate().fold(
+                    T::zero(),
+                    |sum, (class, (logit, target))| {
+                        let log_p = *logit - log_sum_exp;
+                        let modulation = (T::one() - log_p.exp()).powf(self.gamma);
+
+                        sum - self.alpha(class) * *target * modulation * log_p
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        self.check_alpha(cols);
+        let mut grads = Vec::with_capacity(logits.len());
+
+        for (logits, targets) in logits.chunks(cols).zip(targets.chunks(cols)) {
+            let log_sum_exp = log_sum_exp(logits);
+
+            // e_c = dloss/dp_c * p_c, hence dloss/dlogit_j = e_j - p_j * sum(e)
+            let e = logits
+                .iter()
+                .zip(targets)
+                .enumerate()
+                .map(|(class, (logit, target))| {
+                    let log_p = *logit - log_sum_exp;
+                    let p = log_p.exp();
+                    let one_minus_p = T::one() - p;
+
+                    let mut e = one_minus_p.powf(self.gamma).neg();
+                    if self.gamma != T::zero() && one_minus_p > T::zero() {
+                        e += self.gamma * one_minus_p.powf(self.gamma - T::one()) * p * log_p;
+                    }
+                    e * self.alpha(class) * *target
+                })
+                .collect::<Vec<T>>();
+
+            let e_sum = e.iter().fold(T::zero(), |sum, e| sum + *e);
+
+            for (logit, e) in logits.iter().zip(e) {
+                grads.push(e - (*logit - log_sum_exp).exp() * e_sum);
+            }
+        }
+        grads
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
@@ -0,0 +1,168 @@
+use custos::{number::Float, CDatatype};
+
+use super::{Loss, Reduction};
+
+/// `max(0, 1 - target * pred)`
+fn margin<T: Float>(pred: T, target: T) -> T {
+    let margin = T::one() - target * pred;
+    if margin > T::zero() {
+        margin
+    } else {
+        T::zero()
+    }
+}
+
+/// Binary hinge loss `max(0, 1 - targets * preds)`, averaged over the columns of a sample.
+/// The targets must be -1 or 1. Returns subgradients, which are 0 at the hinge.
+#[derive(Debug, Clone, Default)]
+pub struct Hinge<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for Hinge<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        sum + margin(*pred, *target)
+                    })
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .iter()
+            .zip(targets)
+            .map(|(pred, target)| {
+                if margin(*pred, *target) > T::zero() {
+                    target.neg() / T::from_usize(cols)
+                } else {
+                    T::zero()
+                }
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Squared hinge loss `max(0, 1 - targets * preds)^2`, averaged over the columns of a sample.
+/// The targets must be -1 or 1.
+#[derive(Debug, Clone, Default)]
+pub struct SquaredHinge<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for SquaredHinge<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        sum + margin(*pred, *target).powi(2)
+                    })
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .iter()
+            .zip(targets)
+            .map(|(pred, target)| {
+                target.neg() * T::two() * margin(*pred, *target) / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Crammer-Singer multi-class hinge loss `max(0, 1 + max_{j != y}(preds_j) - preds_y)`.
+/// The targets are one-hot encoded, `y` is the target class.
+///
+/// The subgradient is -1 for the target class and 1 for the highest scoring other class, if the margin is violated.
+#[derive(Debug, Clone, Default)]
+pub struct MultiClassHinge<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+/// Returns the index of the target class and of the highest scoring other class.
+fn target_and_rival<T: Float>(preds: &[T], targets: &[T]) -> (usize, usize) {
+    let target = targets
+        .iter()
+        .position(|target| *target > T::zero())
+        .expect("Every sample needs a target class, but a target row is all zeros.");
+
+    let rival = (0..preds.len())
+        .filter(|class| *class != target)
+        .fold(None, |rival: Option<usize>, class| match rival {
+            Some(rival) if preds[rival] >= preds[class] => Some(rival),
+            _ => Some(class),
+        })
+        .expect("At least two classes are needed.");
+
+    (target, rival)
+}
+
+impl<T: Float + CDatatype> Loss<T> for MultiClassHinge<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                let (target, rival) = target_and_rival(preds, targets);
+                margin(preds[target] - preds[rival], T::one())
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let mut grads = vec![T::zero(); preds.len()];
+
+        for (row, (preds, targets)) in preds.chunks(cols).zip(targets.chunks(cols)).enumerate() {
+            let (target, rival) = target_and_rival(preds, targets);
+
+            if margin(preds[target] - preds[rival], T::one()) > T::zero() {
+                grads[row * cols + target] = T::one().neg();
+                grads[row * cols + rival] = T::one();
+            }
+        }
+        grads
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
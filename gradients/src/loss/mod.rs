@@ -0,0 +1,189 @@
+mod bce;
+mod cce;
+mod divergence;
+mod embedding;
+mod focal;
+mod hinge;
+mod mse;
+mod nll;
+mod regression;
+mod softmax_ce;
+mod sparse_cce;
+
+pub use bce::*;
+pub use cce::*;
+pub use divergence::*;
+pub use embedding::*;
+pub use focal::*;
+pub use hinge::*;
+pub use mse::*;
+pub use nll::*;
+pub use regression::*;
+pub use softmax_ce::*;
+pub use sparse_cce::*;
+
+use custos::{number::Float, Alloc, CDatatype, GraphReturn};
+use custos_math::Matrix;
+
+/// Specifies how the sample losses are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reduction {
+    /// The (weighted) sample losses are averaged over the number of samples.
+    #[default]
+    Mean,
+    /// The (weighted) sample losses are summed up.
+    Sum,
+    /// Every sample keeps its own loss.
+    None,
+}
+
+/// A loss function, which is defined on a per sample (row) basis.
+/// [`Loss::loss`] and [`Loss::grad`] apply the reduction and the sample weights.
+///
+/// # Example
+/// ```
+/// use gradients::{Loss, Matrix, MSE, Reduction, CPU};
+///
+/// let device = CPU::new();
+///
+/// let preds = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+/// let targets = Matrix::from((&device, (2, 2), [1., 1., 2., 2.]));
+///
+/// let mse = MSE {
+///     reduction: Reduction::Sum,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(mse.loss(&device, &preds, &targets).read(), vec![3.]);
+/// ```
+pub trait Loss<T: Float + CDatatype> {
+    /// Returns the loss of every sample.
+    /// `cols` is the number of columns of `preds`.
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T>;
+
+    /// Returns the gradient of every sample loss with respect to `preds`.
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T>;
+
+    fn reduction(&self) -> Reduction {
+        Reduction::Mean
+    }
+
+    /// Weights the loss of each sample. The number of weights must match the number of samples.
+    fn weights(&self) -> Option<&[T]> {
+        None
+    }
+
+    /// Returns a 1x1 matrix for [`Reduction::Mean`] and [`Reduction::Sum`] or a (samples x 1) matrix for [`Reduction::None`].
+    fn loss<'a, D: Alloc<T> + GraphReturn>(
+        &self,
+        device: &'a D,
+        preds: &Matrix<T>,
+        targets: &Matrix<T>,
+    ) -> Matrix<'a, T> {
+        let mut losses = self.sample_losses(&preds.read(), &targets.read(), preds.cols());
+        let scales = sample_scales(self.weights(), self.reduction(), preds.rows());
+
+        for (loss, scale) in losses.iter_mut().zip(&scales) {
+            *loss *= *scale;
+        }
+
+        if self.reduction() == Reduction::None {
+            return Matrix::from((device, (losses.len(), 1), losses));
+        }
+
+        let loss = losses.into_iter().fold(T::zero(), |sum, loss| sum + loss);
+        Matrix::from((device, (1, 1), vec![loss]))
+    }
+
+    fn grad<'a, D: Alloc<T> + GraphReturn>(
+        &self,
+        device: &'a D,
+        preds: &Matrix<T>,
+        targets: &Matrix<T>,
+    ) -> Matrix<'a, T> {
+        let cols = preds.cols();
+        let mut grads = self.sample_grads(&preds.read(), &targets.read(), cols);
+        let scales = sample_scales(self.weights(), self.reduction(), preds.rows());
+
+        for (grads, scale) in grads.chunks_mut(cols).zip(&scales) {
+            for grad in grads {
+                *grad *= *scale;
+            }
+        }
+
+        Matrix::from((device, preds.dims(), grads))
+    }
+}
+
+/// Combines the sample weights with the reduction into one factor per sample.
+fn sample_scales<T: Float>(weights: Option<&[T]>, reduction: Reduction, samples: usize) -> Vec<T> {
+    let mut scales = match weights {
+        Some(weights) => {
+            assert_eq!(
+                weights.len(),
+                samples,
+                "The number of sample weights must match the number of samples."
+            );
+            weights.to_vec()
+        }
+        None => vec![T::one(); samples],
+    };
+
+    if reduction == Reduction::Mean {
+        for scale in &mut scales {
+            *scale /= T::from_usize(samples);
+        }
+    }
+    scales
+}
+
+/// Returns the class of a sparse target. Panics, if the target is not a valid class index.
+pub(crate) fn class_index<T: Float>(target: T, cols: usize) -> usize {
+    let class = target.as_usize();
+    assert!(
+        target >= T::zero() && class < cols,
+        "The class index {class} is out of range for {cols} classes."
+    );
+    class
+}
+
+/// Turns one-hot (or class index, if `sparse` is set) targets into label smoothed and class weighted targets.
+/// The result is always one-hot shaped.
+pub(crate) fn class_targets<T: Float>(
+    targets: &[T],
+    cols: usize,
+    sparse: bool,
+    class_weights: Option<&[T]>,
+    label_smoothing: T,
+) -> Vec<T> {
+    let mut class_targets = if sparse {
+        let mut onehot = vec![T::zero(); targets.len() * cols];
+        for (row, target) in targets.iter().enumerate() {
+            onehot[row * cols + class_index(*target, cols)] = T::one();
+        }
+        onehot
+    } else {
+        targets.to_vec()
+    };
+
+    if let Some(class_weights) = class_weights {
+        assert_eq!(
+            class_weights.len(),
+            cols,
+            "The number of class weights must match the number of classes."
+        );
+    }
+
+    let smooth = label_smoothing / T::from_usize(cols);
+
+    for targets in class_targets.chunks_mut(cols) {
+        for (class, target) in targets.iter_mut().enumerate() {
+            *target = *target * (T::one() - label_smoothing) + smooth;
+
+            if let Some(class_weights) = class_weights {
+                *target *= class_weights[class];
+            }
+        }
+    }
+    class_targets
+}
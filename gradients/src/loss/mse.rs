@@ -0,0 +1,45 @@
+use custos::{number::Float, CDatatype};
+
+use super::{Loss, Reduction};
+
+/// Mean squared error. The loss of a sample is the mean of its squared errors.
+/// With [`Reduction::Mean`], this matches `mse` and `mse_grad`.
+#[derive(Debug, Clone, Default)]
+pub struct MSE<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for MSE<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(preds, targets)| {
+                preds
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (pred, target)| {
+                        sum + (*pred - *target) * (*pred - *target)
+                    })
+                    / T::from_usize(cols)
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        preds
+            .iter()
+            .zip(targets)
+            .map(|(pred, target)| (*pred - *target) * T::two() / T::from_usize(cols))
+            .collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
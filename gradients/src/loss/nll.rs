@@ -0,0 +1,53 @@
+use custos::{number::Float, CDatatype};
+use custos_math::Matrix;
+
+use super::{Loss, Reduction};
+
+/// Negative log-likelihood loss, averaged over the samples (rows).
+/// `log_probs` are expected to be log-probabilities, e.g. the output of a [`LogSoftmax`](crate::LogSoftmax) layer.
+pub fn nll<T: Float + CDatatype>(log_probs: &Matrix<T>, targets: &Matrix<T>) -> T {
+    (log_probs * targets).sum().neg() / T::from_usize(log_probs.rows())
+}
+
+pub fn nll_grad<'a, T: Float + CDatatype>(
+    log_probs: &Matrix<'a, T>,
+    targets: &Matrix<'a, T>,
+) -> Matrix<'a, T> {
+    targets * (T::one().neg() / T::from_usize(log_probs.rows()))
+}
+
+/// Negative log-likelihood as a [`Loss`]. The predictions are log-probabilities.
+#[derive(Debug, Clone, Default)]
+pub struct NLL<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for NLL<T> {
+    fn sample_losses(&self, log_probs: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        log_probs
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(log_probs, targets)| {
+                log_probs
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (log_prob, target)| {
+                        sum - *log_prob * *target
+                    })
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, _log_probs: &[T], targets: &[T], _cols: usize) -> Vec<T> {
+        targets.iter().map(|target| target.neg()).collect()
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
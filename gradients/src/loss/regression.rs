@@ -0,0 +1,219 @@
+use custos::{number::Float, CDatatype};
+
+use super::{Loss, Reduction};
+
+/// Applies `loss` to every (pred, target) pair of a sample and averages over the columns.
+fn mean_cols<T: Float>(
+    preds: &[T],
+    targets: &[T],
+    cols: usize,
+    loss: impl Fn(T, T) -> T,
+) -> Vec<T> {
+    preds
+        .chunks(cols)
+        .zip(targets.chunks(cols))
+        .map(|(preds, targets)| {
+            preds
+                .iter()
+                .zip(targets)
+                .fold(T::zero(), |sum, (pred, target)| sum + loss(*pred, *target))
+                / T::from_usize(cols)
+        })
+        .collect()
+}
+
+/// Applies `grad` to every (pred, target) pair and divides by the number of columns.
+fn grad_cols<T: Float>(
+    preds: &[T],
+    targets: &[T],
+    cols: usize,
+    grad: impl Fn(T, T) -> T,
+) -> Vec<T> {
+    preds
+        .iter()
+        .zip(targets)
+        .map(|(pred, target)| grad(*pred, *target) / T::from_usize(cols))
+        .collect()
+}
+
+fn sign<T: Float>(x: T) -> T {
+    if x > T::zero() {
+        T::one()
+    } else if x < T::zero() {
+        T::one().neg()
+    } else {
+        T::zero()
+    }
+}
+
+/// Mean absolute error. The (sub)gradient at zero error is 0.
+#[derive(Debug, Clone, Default)]
+pub struct MAE<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for MAE<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        mean_cols(preds, targets, cols, |pred, target| (pred - target).abs())
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        grad_cols(preds, targets, cols, |pred, target| sign(pred - target))
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Huber loss: quadratic for errors up to `delta`, linear above.
+/// Defaults to a `delta` of 1.
+#[derive(Debug, Clone)]
+pub struct Huber<T> {
+    pub delta: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T> Huber<T> {
+    pub fn new(delta: T) -> Self {
+        Huber {
+            delta,
+            reduction: Reduction::Mean,
+            weights: None,
+        }
+    }
+}
+
+impl<T: Float> Default for Huber<T> {
+    fn default() -> Self {
+        Huber::new(T::one())
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for Huber<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let half = T::one() / T::two();
+
+        mean_cols(preds, targets, cols, |pred, target| {
+            let error = (pred - target).abs();
+            if error <= self.delta {
+                half * error * error
+            } else {
+                self.delta * (error - half * self.delta)
+            }
+        })
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        grad_cols(preds, targets, cols, |pred, target| {
+            let error = pred - target;
+            if error.abs() <= self.delta {
+                error
+            } else {
+                self.delta * sign(error)
+            }
+        })
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Log-cosh loss, `log(cosh(preds - targets))`. Behaves like [`MSE`](crate::MSE) for small and like [`MAE`] for large errors.
+#[derive(Debug, Clone, Default)]
+pub struct LogCosh<T> {
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float + CDatatype> Loss<T> for LogCosh<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        mean_cols(preds, targets, cols, |pred, target| {
+            // log(cosh(x)) = |x| + log(1 + exp(-2|x|)) - log(2), which does not overflow for large x
+            let error = (pred - target).abs();
+            error + (T::one() + (error * T::two()).neg().exp()).ln() - T::two().ln()
+        })
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        grad_cols(preds, targets, cols, |pred, target| (pred - target).tanh())
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Quantile (pinball) loss for the given `quantile` in (0, 1).
+/// Training with e.g. 0.05 and 0.95 yields the bounds of a 90% prediction interval.
+/// Defaults to the median (0.5).
+#[derive(Debug, Clone)]
+pub struct Quantile<T> {
+    pub quantile: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T> Quantile<T> {
+    pub fn new(quantile: T) -> Self {
+        Quantile {
+            quantile,
+            reduction: Reduction::Mean,
+            weights: None,
+        }
+    }
+}
+
+impl<T: Float> Default for Quantile<T> {
+    fn default() -> Self {
+        Quantile::new(T::one() / T::two())
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for Quantile<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        mean_cols(preds, targets, cols, |pred, target| {
+            let error = target - pred;
+            if error >= T::zero() {
+                self.quantile * error
+            } else {
+                (self.quantile - T::one()) * error
+            }
+        })
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        grad_cols(preds, targets, cols, |pred, target| {
+            if target > pred {
+                self.quantile.neg()
+            } else if target < pred {
+                T::one() - self.quantile
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
@@ -0,0 +1,88 @@
+use custos::{number::Float, CDatatype};
+use custos_math::Matrix;
+
+use super::{class_targets, Loss, Reduction};
+use crate::{layers::log_sum_exp, nll, LogSoftmaxMat};
+
+/// Fused softmax and categorical cross-entropy, computed on unnormalised logits.
+/// Returns the loss (averaged over the samples) and the gradient with respect to the logits, `(softmax(logits) - targets) / samples`.
+///
+/// Therefore, a network trained with this loss does not need a final [`Softmax`](crate::Softmax) layer.
+pub fn softmax_cross_entropy<'a, T: Float + CDatatype>(
+    logits: &Matrix<'a, T>,
+    targets: &Matrix<'a, T>,
+) -> (T, Matrix<'a, T>) {
+    let log_probs = logits.log_softmax();
+    let loss = nll(&log_probs, targets);
+
+    // the softmax is recovered from the log-probabilities, hence the logits are only normalised once
+    let grad = (log_probs.exp() - targets) / T::from_usize(logits.rows());
+
+    (loss, grad)
+}
+
+/// [`softmax_cross_entropy`] as a [`Loss`]. The predictions are logits.
+///
+/// `class_weights` and `label_smoothing` are applied to the targets like in [`CCE`](crate::CCE).
+#[derive(Debug, Clone, Default)]
+pub struct SoftmaxCrossEntropy<T> {
+    pub class_weights: Option<Vec<T>>,
+    pub label_smoothing: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> SoftmaxCrossEntropy<T> {
+    fn class_targets(&self, targets: &[T], cols: usize) -> Vec<T> {
+        class_targets(
+            targets,
+            cols,
+            false,
+            self.class_weights.as_deref(),
+            self.label_smoothing,
+        )
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for SoftmaxCrossEntropy<T> {
+    fn sample_losses(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let targets = self.class_targets(targets, cols);
+
+        logits
+            .chunks(cols)
+            .zip(targets.chunks(cols))
+            .map(|(logits, targets)| {
+                let log_sum_exp = log_sum_exp(logits);
+                logits
+                    .iter()
+                    .zip(targets)
+                    .fold(T::zero(), |sum, (logit, target)| {
+                        sum - (*logit - log_sum_exp) * *target
+                    })
+            })
+            .collect()
+    }
+
+    fn sample_grads(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        let targets = self.class_targets(targets, cols);
+        let mut grads = Vec::with_capacity(logits.len());
+
+        for (logits, targets) in logits.chunks(cols).zip(targets.chunks(cols)) {
+            let log_sum_exp = log_sum_exp(logits);
+            let target_sum = targets.iter().fold(T::zero(), |sum, target| sum + *target);
+
+            for (logit, target) in logits.iter().zip(targets) {
+                grads.push((*logit - log_sum_exp).exp() * target_sum - *target);
+            }
+        }
+        grads
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
@@ -0,0 +1,398 @@
+use custos::{get_device, number::Float, CDatatype, CacheBuf, CPU};
+use custos_math::Matrix;
+
+use super::{cce::clip_prob, class_index, class_targets, Loss, Reduction};
+use crate::layers::log_sum_exp;
+use crate::{SoftmaxCrossEntropy, CCE};
+
+#[cfg(feature = "cuda")]
+use custos::cuda::launch_kernel1d;
+
+#[cfg(feature = "opencl")]
+use custos::{opencl::enqueue_kernel, CLDevice};
+
+/// Expands the class indices to one-hot encoded targets, on the host.
+fn onehot<T: Float>(targets: &[T], cols: usize) -> Vec<T> {
+    class_targets(targets, cols, true, None, T::zero())
+}
+
+/// Categorical cross-entropy with class indices as targets, averaged over the samples.
+/// `targets` is a (samples x 1) matrix, hence no one-hot encoded matrix needs to be allocated.
+///
+/// The sample losses are computed on the device of `preds`.
+/// Out-of-range class indices panic on the CPU. On other devices, the loss of such a sample is NaN.
+pub fn sparse_cce<T: Float + CDatatype>(preds: &Matrix<T>, targets: &Matrix<T>) -> T {
+    assert!(targets.cols() == 1 && targets.rows() == preds.rows());
+
+    let losses = get_device!(preds.device, SparseCCEOp<T>).sparse_cce(preds, targets);
+    losses.sum() / T::from_usize(preds.rows())
+}
+
+/// The gradient of [`sparse_cce`]. It is computed on the device of `preds`.
+/// Out-of-range class indices panic on the CPU. On other devices, the gradient of such a sample is NaN.
+pub fn sparse_cce_grad<'a, T: Float + CDatatype>(
+    preds: &Matrix<'a, T>,
+    targets: &Matrix<'a, T>,
+) -> Matrix<'a, T> {
+    assert!(targets.cols() == 1 && targets.rows() == preds.rows());
+    get_device!(preds.device, SparseCCEOp<T>).sparse_cce_grad(preds, targets)
+}
+
+/// Sparse categorical cross-entropy as a [`Loss`]. The predictions are probabilities and the targets class indices.
+///
+/// `class_weights` and `label_smoothing` are applied like in [`CCE`].
+#[derive(Debug, Clone, Default)]
+pub struct SparseCCE<T> {
+    pub class_weights: Option<Vec<T>>,
+    pub label_smoothing: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> SparseCCE<T> {
+    /// Returns the equivalent [`CCE`] on one-hot targets, if class weights or label smoothing are used.
+    fn dense(&self) -> Option<CCE<T>> {
+        if self.class_weights.is_none() && self.label_smoothing == T::zero() {
+            return None;
+        }
+        Some(CCE {
+            class_weights: self.class_weights.clone(),
+            label_smoothing: self.label_smoothing,
+            reduction: self.reduction,
+            weights: None,
+        })
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for SparseCCE<T> {
+    fn sample_losses(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        if let Some(dense) = self.dense() {
+            return dense.sample_losses(preds, &onehot(targets, cols), cols);
+        }
+
+        preds
+            .chunks(cols)
+            .zip(targets)
+            .map(|(preds, target)| clip_prob(preds[class_index(*target, cols)]).ln().neg())
+            .collect()
+    }
+
+    fn sample_grads(&self, preds: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        if let Some(dense) = self.dense() {
+            return dense.sample_grads(preds, &onehot(targets, cols), cols);
+        }
+
+        let mut grads = vec![T::zero(); preds.len()];
+
+        for (row, target) in targets.iter().enumerate() {
+            let idx = row * cols + class_index(*target, cols);
+            grads[idx] = (T::one() / clip_prob(preds[idx])).neg();
+        }
+        grads
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// Fused softmax and sparse categorical cross-entropy on logits, see [`SoftmaxCrossEntropy`].
+#[derive(Debug, Clone, Default)]
+pub struct SparseSoftmaxCrossEntropy<T> {
+    pub class_weights: Option<Vec<T>>,
+    pub label_smoothing: T,
+    pub reduction: Reduction,
+    pub weights: Option<Vec<T>>,
+}
+
+impl<T: Float> SparseSoftmaxCrossEntropy<T> {
+    /// Returns the equivalent [`SoftmaxCrossEntropy`] on one-hot targets, if class weights or label smoothing are used.
+    fn dense(&self) -> Option<SoftmaxCrossEntropy<T>> {
+        if self.class_weights.is_none() && self.label_smoothing == T::zero() {
+            return None;
+        }
+        Some(SoftmaxCrossEntropy {
+            class_weights: self.class_weights.clone(),
+            label_smoothing: self.label_smoothing,
+            reduction: self.reduction,
+            weights: None,
+        })
+    }
+}
+
+impl<T: Float + CDatatype> Loss<T> for SparseSoftmaxCrossEntropy<T> {
+    fn sample_losses(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        if let Some(dense) = self.dense() {
+            return dense.sample_losses(logits, &onehot(targets, cols), cols);
+        }
+
+        logits
+            .chunks(cols)
+            .zip(targets)
+            .map(|(logits, target)| log_sum_exp(logits) - logits[class_index(*target, cols)])
+            .collect()
+    }
+
+    fn sample_grads(&self, logits: &[T], targets: &[T], cols: usize) -> Vec<T> {
+        if let Some(dense) = self.dense() {
+            return dense.sample_grads(logits, &onehot(targets, cols), cols);
+        }
+
+        let mut grads = Vec::with_capacity(logits.len());
+
+        for (logits, target) in logits.chunks(cols).zip(targets) {
+            let log_sum_exp = log_sum_exp(logits);
+            let target = class_index(*target, cols);
+
+            for (idx, logit) in logits.iter().enumerate() {
+                let mut grad = (*logit - log_sum_exp).exp();
+                if idx == target {
+                    grad -= T::one();
+                }
+                grads.push(grad);
+            }
+        }
+        grads
+    }
+
+    fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    fn weights(&self) -> Option<&[T]> {
+        self.weights.as_deref()
+    }
+}
+
+/// The kernels of OpenCL and CUDA write NaN for samples with out-of-range class indices, because they can't panic.
+pub trait SparseCCEOp<T> {
+    /// Returns the (samples x 1) matrix of the sample losses.
+    fn sparse_cce(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T>;
+    fn sparse_cce_grad(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T>;
+}
+
+impl<T: Float + CDatatype> SparseCCEOp<T> for CPU {
+    fn sparse_cce(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let cols = preds.cols();
+
+        let mut losses = self.cached(preds.rows());
+
+        for (row, target) in targets.iter().enumerate() {
+            let pred = preds[row * cols + class_index(*target, cols)];
+            losses[row] = clip_prob(pred).ln().neg();
+        }
+
+        (losses, preds.rows(), 1).into()
+    }
+
+    fn sparse_cce_grad(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let cols = preds.cols();
+        let samples = T::from_usize(preds.rows());
+
+        let mut grad = self.cached(preds.size());
+
+        for (row, target) in targets.iter().enumerate() {
+            let target = class_index(*target, cols);
+
+            for col in 0..cols {
+                let idx = row * cols + col;
+                if col == target {
+                    grad[idx] = (T::one() / (clip_prob(preds[idx]) * samples)).neg();
+                } else {
+                    grad[idx] = T::zero();
+                }
+            }
+        }
+
+        (grad, preds.dims()).into()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl<T: CDatatype> SparseCCEOp<T> for CLDevice {
+    fn sparse_cce(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let src = format!(
+            "
+            __kernel void sparse_cce(
+                __global const {dt}* preds,
+                __global const {dt}* targets,
+                __global {dt}* losses,
+                const int cols)
+                {{
+                    int row = get_global_id(0);
+                    {dt} target = targets[row];
+
+                    if (!(target >= ({dt}) 0 && target < ({dt}) cols)) {{
+                        losses[row] = ({dt}) NAN;
+                        return;
+                    }}
+
+                    {dt} pred = clamp(preds[row * cols + (int) target], ({dt}) 1e-7, ({dt}) 1 - ({dt}) 1e-7);
+                    losses[row] = -log(pred);
+                }}
+        ",
+            dt = T::as_c_type_str()
+        );
+
+        let losses = Matrix::new(self, (preds.rows(), 1));
+
+        enqueue_kernel(
+            self,
+            &src,
+            [preds.rows(), 0, 0],
+            None,
+            &[preds, targets, &losses, &(preds.cols() as i32)],
+        )
+        .unwrap();
+
+        losses
+    }
+
+    fn sparse_cce_grad(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let src = format!(
+            "
+            __kernel void sparse_cce_grad(
+                __global const {dt}* preds,
+                __global const {dt}* targets,
+                __global {dt}* grad,
+                const int cols,
+                const int rows)
+                {{
+                    int idx = get_global_id(0);
+                    {dt} target = targets[idx / cols];
+
+                    if (!(target >= ({dt}) 0 && target < ({dt}) cols)) {{
+                        grad[idx] = ({dt}) NAN;
+                        return;
+                    }}
+
+                    {dt} pred = clamp(preds[idx], ({dt}) 1e-7, ({dt}) 1 - ({dt}) 1e-7);
+                    grad[idx] = (idx % cols == (int) target) ? ({dt}) -1 / (pred * ({dt}) rows) : ({dt}) 0;
+                }}
+        ",
+            dt = T::as_c_type_str()
+        );
+
+        let grad = Matrix::new(self, preds.dims());
+
+        enqueue_kernel(
+            self,
+            &src,
+            [preds.size(), 0, 0],
+            None,
+            &[
+                preds,
+                targets,
+                &grad,
+                &(preds.cols() as i32),
+                &(preds.rows() as i32),
+            ],
+        )
+        .unwrap();
+
+        grad
+    }
+}
+
+#[cfg(feature = "cuda")]
+impl<T: CDatatype> SparseCCEOp<T> for custos::CudaDevice {
+    fn sparse_cce(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let src = format!(
+            r#"extern "C" __global__ void sparse_cce(
+                {dt}* preds,
+                {dt}* targets,
+                {dt}* losses,
+                int cols,
+                int rows
+            )
+                {{
+                    int row = blockDim.x * blockIdx.x + threadIdx.x;
+                    if (row < rows) {{
+                        {dt} target = targets[row];
+
+                        if (!(target >= ({dt}) 0 && target < ({dt}) cols)) {{
+                            losses[row] = ({dt}) nan("");
+                            return;
+                        }}
+
+                        {dt} pred = min(max(preds[row * cols + (int) target], ({dt}) 1e-7), ({dt}) 1 - ({dt}) 1e-7);
+                        losses[row] = -log(pred);
+                    }}
+                }}
+        "#,
+            dt = T::as_c_type_str()
+        );
+
+        let losses = Matrix::new(self, (preds.rows(), 1));
+
+        launch_kernel1d(
+            preds.rows(),
+            self,
+            &src,
+            "sparse_cce",
+            &[
+                &preds.as_buf(),
+                &targets.as_buf(),
+                &losses.as_buf(),
+                &(preds.cols() as i32),
+                &(preds.rows() as i32),
+            ],
+        )
+        .unwrap();
+
+        losses
+    }
+
+    fn sparse_cce_grad(&self, preds: &Matrix<'_, T>, targets: &Matrix<'_, T>) -> Matrix<'_, T> {
+        let src = format!(
+            r#"extern "C" __global__ void sparse_cce_grad(
+                {dt}* preds,
+                {dt}* targets,
+                {dt}* grad,
+                int cols,
+                int rows,
+                int numElements
+            )
+                {{
+                    int idx = blockDim.x * blockIdx.x + threadIdx.x;
+                    if (idx < numElements) {{
+                        {dt} target = targets[idx / cols];
+
+                        if (!(target >= ({dt}) 0 && target < ({dt}) cols)) {{
+                            grad[idx] = ({dt}) nan("");
+                            return;
+                        }}
+
+                        {dt} pred = min(max(preds[idx], ({dt}) 1e-7), ({dt}) 1 - ({dt}) 1e-7);
+                        grad[idx] = (idx % cols == (int) target) ? ({dt}) -1 / (pred * ({dt}) rows) : ({dt}) 0;
+                    }}
+                }}
+        "#,
+            dt = T::as_c_type_str()
+        );
+
+        let grad = Matrix::new(self, preds.dims());
+
+        launch_kernel1d(
+            preds.size(),
+            self,
+            &src,
+            "sparse_cce_grad",
+            &[
+                &preds.as_buf(),
+                &targets.as_buf(),
+                &grad.as_buf(),
+                &(preds.cols() as i32),
+                &(preds.rows() as i32),
+                &preds.size(),
+            ],
+        )
+        .unwrap();
+
+        grad
+    }
+}
@@ -0,0 +1,84 @@
+use crate::LayerShape;
+
+/// The number of values an optimizer stores for every trainable parameter, e.g. the momentum.
+pub trait OptimizerState {
+    fn state_per_param(&self) -> usize;
+}
+
+/// An estimate of the memory, which is needed to train a network.
+/// It is returned by [`NeuralNetwork::memory_footprint`](crate::NeuralNetwork::memory_footprint).
+///
+/// # Example
+/// ```
+/// use gradients::{prelude::*, NeuralNetwork};
+///
+/// #[network]
+/// struct Net {
+///     lin1: Linear<2, 8>,
+///     relu1: ReLU,
+///     lin2: Linear<8, 1>,
+/// }
+///
+/// let device = CPU::new();
+/// let net = Net::<f32>::with(&device);
+///
+/// let adam = net.memory_footprint(&Adam::new(0.001), 64);
+/// let sgd = net.memory_footprint(&SGD::new(0.1).momentum(0.), 64);
+///
+/// assert_eq!(adam.params, 2 * 8 + 8 + 8 + 1);
+/// assert_eq!(adam.optimizer_bytes, 2 * adam.param_bytes);
+/// assert_eq!(sgd.optimizer_bytes, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// The number of trainable parameters.
+    pub params: usize,
+    /// The size of the weights and biases in bytes.
+    pub param_bytes: usize,
+    /// The size of the gradients of the weights and biases in bytes.
+    pub gradient_bytes: usize,
+    /// The size of the state of the optimizer in bytes.
+    pub optimizer_bytes: usize,
+    /// The size of the matrices, which the layers keep from the forward pass, in bytes.
+    pub activation_bytes: usize,
+}
+
+impl MemoryFootprint {
+    /// Estimates the memory from the shapes of the layers, which are given in the order they are executed.
+    /// Every layer keeps its inputs (or its outputs of the same size) for the backward pass, which is counted as activations.
+    /// Layers with an unknown input size are not counted as activations.
+    pub fn new<T>(
+        layers: &[&dyn LayerShape],
+        optimizer: &dyn OptimizerState,
+        batch_size: usize,
+    ) -> Self {
+        let bytes = std::mem::size_of::<T>();
+
+        let mut params = 0;
+        let mut activations = 0;
+        let mut size = None;
+
+        for layer in layers {
+            let input_size = layer.input_size().or(size);
+
+            params += layer.param_count();
+            activations += input_size.unwrap_or(0) * batch_size;
+
+            size = layer.output_size(input_size);
+        }
+
+        MemoryFootprint {
+            params,
+            param_bytes: params * bytes,
+            // every parameter has a gradient of the same shape
+            gradient_bytes: params * bytes,
+            optimizer_bytes: optimizer.state_per_param() * params * bytes,
+            activation_bytes: activations * bytes,
+        }
+    }
+
+    /// The memory needed to train the network in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.param_bytes + self.gradient_bytes + self.optimizer_bytes + self.activation_bytes
+    }
+}
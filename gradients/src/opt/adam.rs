@@ -1,4 +1,4 @@
-use crate::Param;
+use crate::{OptimizerState, Param};
 use custos::{number::Float, Alloc, CDatatype, GraphReturn, CPU};
 use custos_math::Matrix;
 
@@ -46,9 +46,16 @@ impl<'a, T: Float> Adam<'a, T> {
                 self.weight_momentum
                     .push(Matrix::new(device, param.weights.dims()));
 
-                if let Some(bias) = &param.bias {
-                    self.bias_cache.push(Matrix::new(device, bias.dims()));
-                    self.bias_momentum.push(Matrix::new(device, bias.dims()));
+                // layers without bias get an empty state to keep the indices aligned
+                match &param.bias {
+                    Some(bias) => {
+                        self.bias_cache.push(Matrix::new(device, bias.dims()));
+                        self.bias_momentum.push(Matrix::new(device, bias.dims()));
+                    }
+                    None => {
+                        self.bias_cache.push(Matrix::default());
+                        self.bias_momentum.push(Matrix::default());
+                    }
                 }
             }
         }
@@ -57,6 +64,13 @@ impl<'a, T: Float> Adam<'a, T> {
     }
 }
 
+/// Adam keeps the momentum and the cache of every parameter.
+impl<'a, T> OptimizerState for Adam<'a, T> {
+    fn state_per_param(&self) -> usize {
+        2
+    }
+}
+
 pub trait AdamOp<'a, T> {
     fn step(&'a self, adam: &mut Adam<'a, T>, params: Vec<Param<'a, T>>);
 }
@@ -95,10 +109,10 @@ impl<'a, T: CDatatype + Float> AdamOp<'a, T> for CPU {
                 adam.lr,
                 adam.iters,
             );
-            if let Some(bias) = &mut param.bias {
+            if let (Some(bias), Some(dbias)) = (&mut param.bias, &param.dbias) {
                 adam_step_cpu(
                     bias,
-                    &param.dbias,
+                    dbias,
                     &mut adam.bias_momentum[idx],
                     &mut adam.bias_cache[idx],
                     adam.beta1,
@@ -164,7 +178,7 @@ impl<'a, T: CDatatype> AdamOp<'a, T> for custos::CudaDevice {
             )
             .unwrap();
 
-            if let Some(bias) = &layer_data.bias {
+            if let (Some(bias), Some(dbias)) = (&layer_data.bias, &layer_data.dbias) {
                 launch_kernel1d(
                     bias.size(),
                     self,
@@ -172,7 +186,7 @@ impl<'a, T: CDatatype> AdamOp<'a, T> for custos::CudaDevice {
                     "adam",
                     &[
                         bias.as_buf(),
-                        &layer_data.dbias.as_buf(),
+                        &dbias.as_buf(),
                         &adam.bias_momentum[idx].as_buf(),
                         &adam.bias_cache[idx].as_buf(),
                         &adam.beta1,
@@ -234,7 +248,7 @@ impl<'a, T: CDatatype> AdamOp<'a, T> for CLDevice {
             )
             .unwrap();
 
-            if let Some(bias) = &layer_data.bias {
+            if let (Some(bias), Some(dbias)) = (&layer_data.bias, &layer_data.dbias) {
                 enqueue_kernel(
                     self,
                     &src,
@@ -242,7 +256,7 @@ impl<'a, T: CDatatype> AdamOp<'a, T> for CLDevice {
                     None,
                     &[
                         bias,
-                        &layer_data.dbias,
+                        dbias,
                         &adam.bias_momentum[idx],
                         &adam.bias_cache[idx],
                         &adam.beta1,
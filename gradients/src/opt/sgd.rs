@@ -1,4 +1,4 @@
-use crate::Param;
+use crate::{OptimizerState, Param};
 use custos::{Alloc, CDatatype, GraphReturn, CPU};
 use custos_math::Matrix;
 
@@ -38,9 +38,12 @@ impl<'a, T: CDatatype> SGD<'a, T> {
                     self.weight_momentum
                         .push(Matrix::new(device, param.weights.dims()));
 
-                    if let Some(bias) = &param.bias {
-                        self.bias_momentum.push(Matrix::new(device, bias.dims()));
-                    }
+                    // layers without bias get an empty momentum to keep the indices aligned
+                    let bias_momentum = match &param.bias {
+                        Some(bias) => Matrix::new(device, bias.dims()),
+                        None => Matrix::default(),
+                    };
+                    self.bias_momentum.push(bias_momentum);
                 }
             }
             return device.step_momentum(self, params);
@@ -49,13 +52,20 @@ impl<'a, T: CDatatype> SGD<'a, T> {
     }
 }
 
+/// The momentum of every parameter is only stored if momentum is used.
+impl<'a, T: CDatatype> OptimizerState for SGD<'a, T> {
+    fn state_per_param(&self) -> usize {
+        usize::from(self.momentum > T::zero())
+    }
+}
+
 pub trait SGDOp<T: CDatatype> {
     fn step(&self, sgd: &mut SGD<T>, params: Vec<Param<T>>) {
         for mut param in params {
             param.weights -= param.dweights * sgd.lr;
 
-            if let Some(mut bias) = param.bias {
-                bias -= param.dbias * sgd.lr;
+            if let (Some(mut bias), Some(dbias)) = (param.bias, param.dbias) {
+                bias -= dbias * sgd.lr;
             }
         }
     }
@@ -72,10 +82,10 @@ impl<T: CDatatype> SGDOp<T> for CPU {
                 sgd.weight_momentum[layer_idx][idx] = update;
             }
 
-            if let Some(bias) = &mut param.bias {
+            if let (Some(bias), Some(dbias)) = (&mut param.bias, &param.dbias) {
                 for (idx, b) in bias.iter_mut().enumerate() {
                     let update = sgd.momentum * sgd.bias_momentum[layer_idx][idx]
-                        + param.dbias[idx] * sgd.lr;
+                        + dbias[idx] * sgd.lr;
                     *b -= update;
                     sgd.bias_momentum[layer_idx][idx] = update;
                 }
@@ -122,7 +132,7 @@ impl<T: CDatatype> SGDOp<T> for CLDevice {
             )
             .unwrap();
 
-            if let Some(bias) = &param.bias {
+            if let (Some(bias), Some(dbias)) = (&param.bias, &param.dbias) {
                 enqueue_kernel(
                     self,
                     &src,
@@ -130,7 +140,7 @@ impl<T: CDatatype> SGDOp<T> for CLDevice {
                     None,
                     &[
                         &bias,
-                        &param.dbias,
+                        dbias,
                         &sgd.bias_momentum[idx],
                         &sgd.momentum,
                         &sgd.lr,
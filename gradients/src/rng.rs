@@ -0,0 +1,104 @@
+use std::{
+    cell::RefCell,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use custos::{number::Float, Alloc, GraphReturn};
+use custos_math::Matrix;
+
+/// A splitmix64 generator, which samples values on the host.
+/// The samples are written to the device afterwards, hence this works for every device.
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Seeds the generator with the random keys of the standard library.
+    pub fn from_entropy() -> Rng {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Rng::new(hasher.finish())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniformly distributed value in `[min, max)`.
+    pub fn uniform<T: Float>(&mut self, min: T, max: T) -> T {
+        min + (max - min) * T::as_generic(self.next_f64())
+    }
+
+    /// A normally distributed value, sampled with the Box-Muller transform.
+    pub fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        // 1 - [0, 1) lies in (0, 1], hence the logarithm is finite
+        let u1 = 1. - self.next_f64();
+        let u2 = self.next_f64();
+        mean + std * (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos()
+    }
+}
+
+thread_local! {
+    static RNG: RefCell<Rng> = RefCell::new(Rng::from_entropy());
+}
+
+/// Calls `f` with the generator of the current thread.
+pub(crate) fn with_rng<R>(f: impl FnOnce(&mut Rng) -> R) -> R {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Seeds the random number generator of the current thread.
+/// It is used by every random operation, e.g. the initialization of the layers.
+/// Hence, networks created after seeding with the same seed have identical parameters and, on the `CPU`, identical losses.
+///
+/// # Example
+/// ```
+/// use gradients::{prelude::*, seed};
+///
+/// let device = CPU::new();
+///
+/// seed(42);
+/// let lin1 = Linear::<f32, 8, 4>::new(&device, ());
+///
+/// seed(42);
+/// let lin2 = Linear::<f32, 8, 4>::new(&device, ());
+///
+/// assert_eq!(lin1.weights.read(), lin2.weights.read());
+/// ```
+pub fn seed(seed: u64) {
+    with_rng(|rng| *rng = Rng::new(seed));
+}
+
+/// A matrix, whose values are sampled from `U(min, max)` on the host.
+/// Unlike `Matrix::rand`, this works for every device and can be seeded.
+pub(crate) fn rand_matrix<'a, T, D>(
+    device: &'a D,
+    dims: (usize, usize),
+    min: T,
+    max: T,
+) -> Matrix<'a, T>
+where
+    T: Float,
+    D: Alloc<T> + GraphReturn,
+{
+    let values = with_rng(|rng| {
+        (0..dims.0 * dims.1)
+            .map(|_| rng.uniform(min, max))
+            .collect::<Vec<T>>()
+    });
+    Matrix::from((device, dims, values))
+}
@@ -0,0 +1,98 @@
+use custos_math::Matrix;
+
+use crate::{Layer, LayerShape, NeuralNetwork, Param};
+
+/// A neural network, whose layers are chosen at runtime.
+/// The layers are executed in the order they were added.
+///
+/// # Example
+/// ```
+/// use gradients::{prelude::*, NeuralNetwork};
+///
+/// let device = CPU::new();
+///
+/// let mut net = Sequential::new()
+///     .add(Linear::<f32, 2, 4>::new(&device, ()))
+///     .add(ReLU::new())
+///     .add(Linear::<f32, 4, 1>::new(&device, ()));
+///
+/// let inputs = Matrix::from((&device, (1, 2), [0.5, -0.5]));
+/// let out = net.forward(&inputs);
+/// assert_eq!(out.dims(), (1, 1));
+/// ```
+pub struct Sequential<'a, T> {
+    pub layers: Vec<Box<dyn Layer<'a, T> + 'a>>,
+}
+
+impl<'a, T> Sequential<'a, T> {
+    pub fn new() -> Self {
+        Sequential { layers: Vec::new() }
+    }
+
+    /// Appends a layer to the network.
+    // a builder method, which is not meant to be `std::ops::Add`
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, layer: impl Layer<'a, T> + 'a) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Appends an already boxed layer to the network.
+    pub fn push(&mut self, layer: Box<dyn Layer<'a, T> + 'a>) {
+        self.layers.push(layer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl<'a, T> Default for Sequential<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> From<Vec<Box<dyn Layer<'a, T> + 'a>>> for Sequential<'a, T> {
+    fn from(layers: Vec<Box<dyn Layer<'a, T> + 'a>>) -> Self {
+        Sequential { layers }
+    }
+}
+
+impl<'a, T> NeuralNetwork<'a, T> for Sequential<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        let mut layers = self.layers.iter_mut();
+        let first = layers
+            .next()
+            .expect("A Sequential network needs at least one layer.");
+
+        layers.fold(first.forward(inputs), |out, layer| layer.forward(&out))
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        let mut layers = self.layers.iter_mut().rev();
+        let last = layers
+            .next()
+            .expect("A Sequential network needs at least one layer.");
+
+        layers.fold(last.backward(grad), |grad, layer| layer.backward(&grad))
+    }
+
+    fn params(&mut self) -> Vec<Param<'a, T>> {
+        self.layers
+            .iter_mut()
+            .filter_map(|layer| layer.params())
+            .collect()
+    }
+
+    fn layer_shapes(&self) -> Vec<&dyn LayerShape> {
+        self.layers
+            .iter()
+            .map(|layer| layer.as_ref() as &dyn LayerShape)
+            .collect()
+    }
+}
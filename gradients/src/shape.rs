@@ -0,0 +1,91 @@
+/// The number of features per sample, that a layer takes and returns.
+/// It is used to check whether the layers of a network fit together and to summarize a network.
+pub trait LayerShape {
+    /// The number of input features, `None` if the layer accepts any size.
+    fn input_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// The number of output features for inputs with `input_size` features.
+    /// `None` stands for an unknown size. By default, the size of the inputs is kept.
+    fn output_size(&self, input_size: Option<usize>) -> Option<usize> {
+        input_size
+    }
+
+    /// The number of parameters (weights and biases) of the layer.
+    fn param_count(&self) -> usize {
+        0
+    }
+}
+
+/// The shape of a layer, which does not implement [`LayerShape`]. Its sizes are unknown.
+#[doc(hidden)]
+pub struct UnknownShape;
+
+impl LayerShape for UnknownShape {
+    fn output_size(&self, _input_size: Option<usize>) -> Option<usize> {
+        None
+    }
+}
+
+/// Returns the shape of a field in the code generated by `#[network]` and `#[derive(NeuralNetwork)]`,
+/// without requiring every field to implement [`LayerShape`].
+///
+/// `(&ShapeOf(&layer)).layer_shape()` resolves to [`HasShape`] if the layer implements [`LayerShape`].
+/// Otherwise, the method is found on the reference through [`NoShape`], which returns an [`UnknownShape`].
+#[doc(hidden)]
+pub struct ShapeOf<'r, L>(pub &'r L);
+
+#[doc(hidden)]
+pub trait HasShape<'r> {
+    fn layer_shape(&self) -> &'r dyn LayerShape;
+}
+
+impl<'r, L: LayerShape> HasShape<'r> for ShapeOf<'r, L> {
+    fn layer_shape(&self) -> &'r dyn LayerShape {
+        self.0
+    }
+}
+
+#[doc(hidden)]
+pub trait NoShape<'r> {
+    fn layer_shape(&self) -> &'r dyn LayerShape {
+        &UnknownShape
+    }
+}
+
+impl<'r, L> NoShape<'r> for &ShapeOf<'r, L> {}
+
+/// Panics if the output size of a layer does not match the input size of the following layer.
+/// The layers are given by their name and their shape.
+///
+/// # Example
+/// ```
+/// use gradients::{check_shapes, prelude::*};
+///
+/// let device = CPU::new();
+///
+/// let lin1 = Linear::<f32, 2, 8>::new(&device, ());
+/// let relu = ReLU::<f32>::new();
+/// let lin2 = Linear::<f32, 8, 1>::new(&device, ());
+///
+/// check_shapes(&[("lin1", &lin1), ("relu", &relu), ("lin2", &lin2)]);
+/// ```
+pub fn check_shapes(layers: &[(&str, &dyn LayerShape)]) {
+    let mut size: Option<(&str, usize)> = None;
+
+    for &(name, layer) in layers {
+        let input_size = layer.input_size();
+
+        if let (Some((prev_name, output_size)), Some(input_size)) = (size, input_size) {
+            assert!(
+                output_size == input_size,
+                "The output size of {prev_name:?} ({output_size}) does not match the input size of {name:?} ({input_size})."
+            );
+        }
+
+        size = layer
+            .output_size(input_size.or(size.map(|(_, size)| size)))
+            .map(|output_size| (name, output_size));
+    }
+}
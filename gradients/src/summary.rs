@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+
+/// A row of a [`Summary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerSummary {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The type of the layer, e.g. `Linear<784, 128>`.
+    pub layer: &'static str,
+    /// The number of input features. `None` if it is unknown.
+    pub input_size: Option<usize>,
+    /// The number of output features. `None` if it is unknown.
+    pub output_size: Option<usize>,
+    pub params: usize,
+}
+
+/// An overview of the layers of a network, which is displayed as a table.
+/// It is returned by [`NeuralNetwork::summary_table`](crate::NeuralNetwork::summary_table).
+///
+/// # Example
+/// ```
+/// use gradients::{prelude::*, NeuralNetwork};
+///
+/// #[network]
+/// struct Net {
+///     lin1: Linear<2, 8>,
+///     relu1: ReLU,
+///     lin2: Linear<8, 1>,
+/// }
+///
+/// let device = CPU::new();
+/// let net = Net::<f32>::with(&device);
+///
+/// let summary = net.summary_table();
+/// assert_eq!(summary.total_params(), 2 * 8 + 8 + 8 + 1);
+/// println!("{summary}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub layers: Vec<LayerSummary>,
+}
+
+impl Summary {
+    pub fn new(layers: Vec<LayerSummary>) -> Self {
+        Summary { layers }
+    }
+
+    /// The number of parameters of all layers.
+    pub fn total_params(&self) -> usize {
+        self.layers.iter().map(|layer| layer.params).sum()
+    }
+}
+
+/// The shape of a batch of samples with `size` features, e.g. `(None, 784)`.
+fn shape(size: Option<usize>) -> String {
+    match size {
+        Some(size) => format!("(None, {size})"),
+        None => "(None, ?)".to_string(),
+    }
+}
+
+fn write_row(f: &mut Formatter<'_>, row: &[String; 5], widths: &[usize; 5]) -> std::fmt::Result {
+    let cells = row
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>();
+    writeln!(f, "{}", cells.join("   ").trim_end())
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let header = ["Field", "Layer", "Input Shape", "Output Shape", "Param #"].map(String::from);
+
+        let rows = self
+            .layers
+            .iter()
+            .map(|layer| {
+                [
+                    layer.name.to_string(),
+                    layer.layer.to_string(),
+                    shape(layer.input_size),
+                    shape(layer.output_size),
+                    layer.params.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = [0; 5];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let separator = "=".repeat(widths.iter().sum::<usize>() + 3 * (widths.len() - 1));
+
+        write_row(f, &header, &widths)?;
+        writeln!(f, "{separator}")?;
+
+        for row in &rows {
+            write_row(f, row, &widths)?;
+        }
+
+        writeln!(f, "{separator}")?;
+        write!(f, "Total params: {}", self.total_params())
+    }
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{assert_approx, check_grad};
+use gradients::{bce_with_logits, BCEWithLogits, Loss, Matrix, CPU};
+
+fn bce(logit: f64, target: f64, pos_weight: f64) -> f64 {
+    let sigmoid = 1. / (1. + (-logit).exp());
+    -(pos_weight * target * sigmoid.ln() + (1. - target) * (1. - sigmoid).ln())
+}
+
+#[test]
+fn test_bce_with_logits() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 2), [0.5f32, -1.5, 2., 0.]));
+    let targets = Matrix::from((&device, (2, 2), [1., 0., 0., 1.]));
+
+    let (loss, grad) = bce_with_logits(&device, &logits, &targets, Some(2.));
+
+    let expected = (bce(0.5, 1., 2.) + bce(-1.5, 0., 2.) + bce(2., 0., 2.) + bce(0., 1., 2.)) / 4.;
+    assert!((loss as f64 - expected).abs() < 1e-5);
+
+    let loss = BCEWithLogits {
+        pos_weight: Some(2.),
+        ..Default::default()
+    };
+    assert_approx(
+        &grad.read(),
+        &loss.grad(&device, &logits, &targets).read(),
+        1e-6,
+    );
+
+    let loss = BCEWithLogits::<f64> {
+        pos_weight: Some(2.),
+        ..Default::default()
+    };
+    check_grad(&loss, &[0.5, -1.5, 2., 0.], &[1., 0., 0., 1.], (2, 2));
+}
+
+#[test]
+fn test_bce_with_large_logits() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (1, 2), [100f32, -100.]));
+    let targets = Matrix::from((&device, (1, 2), [0., 1.]));
+
+    let bce = BCEWithLogits::default();
+    let loss = bce.loss(&device, &logits, &targets).read()[0];
+    assert!((loss - 100.).abs() < 1e-3);
+
+    let grad = bce.grad(&device, &logits, &targets).read();
+    assert_eq!(grad, vec![0.5, -0.5]);
+}
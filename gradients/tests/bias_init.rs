@@ -0,0 +1,88 @@
+use gradients::{linear::BiasInit, prelude::*};
+
+#[test]
+fn test_bias_init() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 4, 8>::new(&device, ());
+    assert_eq!(lin.bias.unwrap().read(), vec![0.; 8]);
+
+    let lin = Linear::<f32, 4, 8>::new(&device, BiasInit::Constant(0.1));
+    assert_eq!(lin.bias.unwrap().read(), vec![0.1; 8]);
+
+    let lin = Linear::<f32, 4, 8>::new(
+        &device,
+        LinearConfig {
+            bias_init: BiasInit::Uniform { min: 0.5, max: 1. },
+            ..Default::default()
+        },
+    );
+    let bias = lin.bias.unwrap().read();
+    assert!(bias.iter().all(|b| (0.5..=1.).contains(b)));
+
+    let lin = Linear::<f32, 4, 8>::new(
+        &device,
+        LinearConfig {
+            init: RandomUniform::new(0.5, 1.),
+            bias_init: BiasInit::Zeros,
+            ..Default::default()
+        },
+    );
+    assert_eq!(lin.bias.unwrap().read(), vec![0.; 8]);
+}
+
+#[test]
+fn test_bias_from_init() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 4, 8>::new(
+        &device,
+        LinearConfig {
+            init: RandomUniform::new(0.5, 1.),
+            bias_init: BiasInit::FromInit,
+            ..Default::default()
+        },
+    );
+    let bias = lin.bias.unwrap().read();
+    assert!(bias.iter().all(|b| (0.5..=1.).contains(b)));
+
+    let lin = DynLinear::<f32>::new(
+        &device,
+        4,
+        8,
+        DynLinearConfig {
+            init: Glorot::new(),
+            bias_init: BiasInit::FromInit,
+            ..Default::default()
+        },
+    );
+    let glorot = (6f32 / 12.).sqrt();
+    let bias = lin.bias.unwrap().read();
+    assert!(bias.iter().all(|b| b.abs() <= glorot));
+    assert!(bias.iter().any(|b| *b != 0.));
+}
+
+#[test]
+fn test_dyn_linear_bias_init() {
+    let device = CPU::new();
+
+    let lin = DynLinear::<f32>::new(&device, 4, 8, BiasInit::Constant(-0.5));
+    assert_eq!(lin.bias.unwrap().read(), vec![-0.5; 8]);
+}
+
+#[network]
+struct Net {
+    #[bias_init(BiasInit::Constant(0.1))]
+    lin1: Linear<4, 8>,
+    relu1: ReLU,
+    lin2: Linear<8, 2>,
+}
+
+#[test]
+fn test_bias_init_attribute() {
+    let device = CPU::new();
+    let net = Net::<f32>::with(&device);
+
+    assert_eq!(net.lin1.bias.as_ref().unwrap().read(), vec![0.1; 8]);
+    assert_eq!(net.lin2.bias.as_ref().unwrap().read(), vec![0.; 2]);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{assert_approx, check_grad};
+use gradients::{
+    Loss, Matrix, OneHotMat, SoftmaxCrossEntropy, SparseCCE, SparseSoftmaxCrossEntropy, CCE, CPU,
+};
+
+#[test]
+fn test_label_smoothing_cce() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (1, 4), [0.1, 0.6, 0.2, 0.1]));
+    let targets = Matrix::from((&device, (1, 4), [0., 1., 0., 0.]));
+
+    let cce = CCE {
+        label_smoothing: 0.2,
+        ..Default::default()
+    };
+
+    // smoothed targets: [0.05, 0.85, 0.05, 0.05]
+    let expected =
+        -(0.05 * 0.1f32.ln() + 0.85 * 0.6f32.ln() + 0.05 * 0.2f32.ln() + 0.05 * 0.1f32.ln());
+    assert_approx(
+        &cce.loss(&device, &preds, &targets).read(),
+        &[expected],
+        1e-5,
+    );
+
+    let expected_grad = [-0.05 / 0.1, -0.85 / 0.6, -0.05 / 0.2, -0.05 / 0.1];
+    assert_approx(
+        &cce.grad(&device, &preds, &targets).read(),
+        &expected_grad,
+        1e-5,
+    );
+}
+
+#[test]
+fn test_sparse_matches_onehot() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((
+        &device,
+        (3, 3),
+        [0.1, 0.7, 0.2, 0.6, 0.3, 0.1, 0.2, 0.2, 0.6],
+    ));
+    let classes = Matrix::from((&device, (3, 1), [1., 0., 2.]));
+    let onehot = classes.onehot();
+
+    let dense = CCE {
+        class_weights: Some(vec![1., 2., 0.5]),
+        label_smoothing: 0.1,
+        ..Default::default()
+    };
+    let sparse = SparseCCE {
+        class_weights: Some(vec![1., 2., 0.5]),
+        label_smoothing: 0.1,
+        ..Default::default()
+    };
+
+    assert_approx(
+        &sparse.loss(&device, &preds, &classes).read(),
+        &dense.loss(&device, &preds, &onehot).read(),
+        1e-6,
+    );
+    assert_approx(
+        &sparse.grad(&device, &preds, &classes).read(),
+        &dense.grad(&device, &preds, &onehot).read(),
+        1e-6,
+    );
+}
+
+#[test]
+fn test_class_weighted_softmax_ce_grad() {
+    let device = CPU::new();
+
+    let logits = [0.3, -1.2, 2., 1.1, 0.4, -0.7];
+    let loss = SparseSoftmaxCrossEntropy {
+        class_weights: Some(vec![3., 1., 0.5]),
+        label_smoothing: 0.1,
+        ..Default::default()
+    };
+    check_grad(&loss, &logits, &[2., 0.], (2, 3));
+
+    let dense = SoftmaxCrossEntropy {
+        class_weights: Some(vec![3., 1., 0.5]),
+        label_smoothing: 0.1,
+        ..Default::default()
+    };
+    let logits = Matrix::from((&device, (2, 3), logits));
+    let classes = Matrix::from((&device, (2, 1), [2., 0.]));
+    assert_approx(
+        &dense.grad(&device, &logits, &classes.onehot()).read(),
+        &loss.grad(&device, &logits, &classes).read(),
+        1e-6,
+    );
+}
@@ -0,0 +1,54 @@
+//! Helpers, which are shared by the loss tests.
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+use gradients::{Loss, Matrix, CPU};
+
+/// Panics if two values at the same index differ by `tolerance` or more.
+pub fn assert_approx<T: Copy + Display + Into<f64>>(lhs: &[T], rhs: &[T], tolerance: f64) {
+    assert_eq!(lhs.len(), rhs.len());
+    for (lhs, rhs) in lhs.iter().zip(rhs) {
+        assert!(
+            ((*lhs).into() - (*rhs).into()).abs() < tolerance,
+            "{lhs} != {rhs}"
+        );
+    }
+}
+
+/// Computes the gradient of `f` at `values` with central differences.
+pub fn numeric_grad(values: &[f64], f: impl Fn(Vec<f64>) -> f64) -> Vec<f64> {
+    let eps = 1e-6;
+    (0..values.len())
+        .map(|idx| {
+            let mut plus = values.to_vec();
+            plus[idx] += eps;
+            let mut minus = values.to_vec();
+            minus[idx] -= eps;
+            (f(plus) - f(minus)) / (2. * eps)
+        })
+        .collect()
+}
+
+/// Compares the gradient of `loss` with central differences.
+/// The predictions have the shape `dims`. The targets have as many rows, e.g. a column of class indices.
+pub fn check_grad<L: Loss<f64>>(loss: &L, preds: &[f64], targets: &[f64], dims: (usize, usize)) {
+    let device = CPU::new();
+
+    let target_dims = (dims.0, targets.len() / dims.0);
+    let targets = Matrix::from((&device, target_dims, targets.to_vec()));
+
+    let grad = loss
+        .grad(
+            &device,
+            &Matrix::from((&device, dims, preds.to_vec())),
+            &targets,
+        )
+        .read();
+
+    let numeric = numeric_grad(preds, |preds| {
+        let preds = Matrix::from((&device, dims, preds));
+        loss.loss(&device, &preds, &targets).read()[0]
+    });
+    assert_approx(&grad, &numeric, 1e-6);
+}
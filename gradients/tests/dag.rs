@@ -0,0 +1,74 @@
+use gradients::{prelude::*, Conv2D, NeuralNetwork};
+
+#[network]
+struct TwoBranches {
+    lin1: Linear<4, 6>,
+    lin_a: Linear<6, 3>,
+    relu_a: ReLU,
+    #[input(lin1)]
+    #[residual(from = "relu_a")]
+    lin_b: Linear<6, 3>,
+    #[output]
+    softmax: Softmax,
+    #[skip]
+    aux: Linear<6, 2>,
+}
+
+#[test]
+fn test_fan_out_forward_backward() {
+    let device = CPU::new();
+
+    let mut net = TwoBranches::<f32>::with(&device);
+
+    let x = Matrix::from((&device, (2, 4), [0.5, -0.2, 0.1, 0.9, -0.3, 0.7, 0.2, -0.6]));
+    let grad = Matrix::from((&device, (2, 3), [0.1, 0.2, -0.3, 0.4, 0.5, -0.1]));
+
+    let lin1_out = net.lin1.forward(&x);
+    let relu_a_out = net.relu_a.forward(&net.lin_a.forward(&lin1_out));
+    let lin_b_out = net.lin_b.forward(&lin1_out) + &relu_a_out;
+    let expected = net.softmax.forward(&lin_b_out);
+
+    let dsoftmax = net.softmax.backward(&grad);
+    let dlin_b = net.lin_b.backward(&dsoftmax);
+    let dlin_a = net.lin_a.backward(&net.relu_a.backward(&dsoftmax));
+    let expected_grad = net.lin1.backward(&(dlin_b + &dlin_a));
+
+    assert_eq!(net.forward(&x).read(), expected.read());
+    assert_eq!(net.backward(&grad).read(), expected_grad.read());
+}
+
+#[test]
+fn test_skipped_fields_are_not_trained() {
+    let device = CPU::new();
+
+    let mut net = TwoBranches::<f32>::with(&device);
+
+    let x = Matrix::from((&device, (1, 4), [0.5, -0.2, 0.1, 0.9]));
+    let out = net.forward(&x);
+    net.backward(&out);
+
+    // lin1, lin_a and lin_b
+    assert_eq!(net.params().len(), 3);
+
+    let aux_out = net.aux.forward(&net.lin1.forward(&x));
+    assert_eq!(aux_out.dims(), (1, 2));
+}
+
+#[network]
+struct MismatchedBranches {
+    #[conv(input = (2, 2), kernel = (1, 1), blocks = 2)]
+    conv: Conv2D,
+    lin_a: Linear<4, 3>,
+    #[input(inputs)]
+    #[residual(from = "lin_a")]
+    lin_b: Linear<4, 3>,
+}
+
+#[test]
+#[should_panic(
+    expected = "The output size of \"conv\" (8) does not match the input size of \"lin_a\" (4)."
+)]
+fn test_shapes_are_checked_outside_custom_inputs() {
+    let device = CPU::new();
+    MismatchedBranches::<f32>::with(&device);
+}
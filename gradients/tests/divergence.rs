@@ -0,0 +1,69 @@
+mod common;
+
+use common::check_grad;
+use gradients::{JSDivergence, KLDivergence, Loss, Matrix, CPU};
+
+const PREDS: [f64; 6] = [0.2, 0.5, 0.3, 0.6, 0.1, 0.3];
+const TARGETS: [f64; 6] = [0.1, 0.6, 0.3, 0.5, 0.5, 0.];
+
+fn log(values: &[f64]) -> Vec<f64> {
+    values.iter().map(|x| x.ln()).collect()
+}
+
+#[test]
+fn test_kl_divergence() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let log_preds = Matrix::from((&device, (2, 3), log(&PREDS)));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let expected = (0.1 * (0.1f64 / 0.2).ln()
+        + 0.6 * (0.6f64 / 0.5).ln()
+        + 0.5 * (0.5f64 / 0.6).ln()
+        + 0.5 * (0.5f64 / 0.1).ln())
+        / 2.;
+
+    let kl = KLDivergence::default()
+        .loss(&device, &preds, &targets)
+        .read();
+    assert!((kl[0] - expected).abs() < 1e-10);
+
+    let log_kl = KLDivergence {
+        log_input: true,
+        ..Default::default()
+    };
+    let kl = log_kl.loss(&device, &log_preds, &targets).read();
+    assert!((kl[0] - expected).abs() < 1e-10);
+
+    check_grad(&KLDivergence::default(), &PREDS, &TARGETS, (2, 3));
+    check_grad(&log_kl, &log(&PREDS), &TARGETS, (2, 3));
+}
+
+#[test]
+fn test_js_divergence() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), PREDS));
+    let targets = Matrix::from((&device, (2, 3), TARGETS));
+
+    let js = JSDivergence::default();
+
+    let lhs = js.loss(&device, &preds, &targets).read()[0];
+    let rhs = js.loss(&device, &targets, &preds).read()[0];
+    assert!((lhs - rhs).abs() < 1e-10);
+    assert!(lhs > 0. && lhs <= 2f64.ln());
+
+    assert_eq!(js.loss(&device, &preds, &preds).read(), vec![0.]);
+
+    check_grad(&js, &PREDS, &TARGETS, (2, 3));
+    check_grad(
+        &JSDivergence {
+            log_input: true,
+            ..Default::default()
+        },
+        &log(&PREDS),
+        &TARGETS,
+        (2, 3),
+    );
+}
@@ -0,0 +1,91 @@
+use gradients::{prelude::*, NeuralNetwork};
+
+#[test]
+fn test_dyn_linear_conversion() {
+    let device = CPU::new();
+
+    let mut linear = Linear::<f32, 2, 3>::new(&device, ());
+    let x = Matrix::from((&device, (2, 2), [0.5, -1., 2., 0.25]));
+    let expected = linear.forward(&x).read();
+
+    let mut dyn_linear = DynLinear::from(linear);
+    assert_eq!(dyn_linear.dims(), (2, 3));
+    assert_eq!(dyn_linear.forward(&x).read(), expected);
+
+    let dyn_linear = match Linear::<f32, 3, 2>::try_from(dyn_linear) {
+        Ok(_) => panic!("the dimensions should not match"),
+        Err(dyn_linear) => dyn_linear,
+    };
+
+    let mut linear = Linear::<f32, 2, 3>::try_from(dyn_linear).ok().unwrap();
+    assert_eq!(linear.forward(&x).read(), expected);
+}
+
+#[test]
+fn test_dyn_linear_config() {
+    let device = CPU::new();
+
+    let linear = DynLinear::<f32>::new(&device, 8, 16, Bias(false));
+    assert!(linear.bias.is_none());
+
+    let l2_reg = L2Reg::new(0.1);
+    let mut linear = DynLinear::<f32>::new(&device, 8, 16, &l2_reg);
+    linear.forward(&Matrix::from((&device, (1, 8), [1.; 8])));
+
+    let weights = linear.weights.read();
+    let expected = weights.iter().map(|w| w * w).sum::<f32>() * 0.1;
+    assert!((*l2_reg.loss.borrow() - expected).abs() < 1e-5);
+}
+
+#[test]
+fn test_dyn_linear_without_bias_trains() {
+    let device = CPU::new();
+
+    let xs = Matrix::from((&device, 4, 2, [0., 0., 0., 1., 1., 0., 1., 1.]));
+    let ys = Matrix::from((&device, 4, 2, [1., 0., 0., 1., 0., 1., 1., 0.]));
+
+    let mut net = Sequential::new()
+        .add(DynLinear::<f32>::new(&device, 2, 8, Bias(false)))
+        .add(Tanh::new())
+        .add(DynLinear::<f32>::new(&device, 8, 2, ()));
+
+    let mut sgd = SGD::new(0.1);
+    let mut adam = Adam::new(1e-3);
+
+    for _ in range(10) {
+        let preds = net.forward(&xs);
+        net.backward(&mse_grad(&preds, &ys));
+        sgd.step(&device, net.params());
+        adam.step(&device, net.params());
+    }
+}
+
+#[test]
+fn test_dyn_linear_hidden_sweep() {
+    let device = CPU::new();
+
+    let xs = Matrix::from((&device, 4, 2, [0., 0., 0., 1., 1., 0., 1., 1.]));
+    let ys = Matrix::from((&device, 4, 2, [1., 0., 0., 1., 0., 1., 1., 0.]));
+
+    for hidden in [4, 8, 16] {
+        let mut net = Sequential::new()
+            .add(DynLinear::<f32>::new(&device, 2, hidden, ()))
+            .add(Tanh::new())
+            .add(DynLinear::<f32>::new(&device, hidden, 2, ()));
+
+        let mut sgd = SGD::new(0.1);
+
+        let first_loss = mse(&net.forward(&xs), &ys);
+        let mut loss = first_loss;
+
+        for _ in range(200) {
+            let preds = net.forward(&xs);
+            loss = mse(&preds, &ys);
+
+            net.backward(&mse_grad(&preds, &ys));
+            sgd.step(&device, net.params());
+        }
+
+        assert!(loss < first_loss);
+    }
+}
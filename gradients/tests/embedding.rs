@@ -0,0 +1,89 @@
+mod common;
+
+use common::{assert_approx, numeric_grad};
+use gradients::{contrastive, triplet, Matrix, CPU};
+
+const LHS: [f64; 6] = [0.5, -1., 2., 0.1, 0.3, 0.2];
+const RHS: [f64; 6] = [1., 0., 1.5, 0.4, 0.1, 0.];
+
+#[test]
+fn test_contrastive() {
+    let device = CPU::new();
+
+    let lhs = Matrix::from((&device, (2, 3), LHS));
+    let rhs = Matrix::from((&device, (2, 3), RHS));
+
+    for labels in [[1., 0.], [0., 1.], [0., 0.]] {
+        let labels = Matrix::from((&device, (2, 1), labels));
+        let (_, dlhs, drhs) = contrastive(&device, &lhs, &rhs, &labels, 2.);
+
+        let loss = |lhs: Vec<f64>, rhs: Vec<f64>| {
+            let lhs = Matrix::from((&device, (2, 3), lhs));
+            let rhs = Matrix::from((&device, (2, 3), rhs));
+            contrastive(&device, &lhs, &rhs, &labels, 2.).0
+        };
+
+        assert_approx(
+            &dlhs.read(),
+            &numeric_grad(&LHS, |lhs| loss(lhs, RHS.to_vec())),
+            1e-6,
+        );
+        assert_approx(
+            &drhs.read(),
+            &numeric_grad(&RHS, |rhs| loss(LHS.to_vec(), rhs)),
+            1e-6,
+        );
+    }
+
+    // dissimilar pairs further apart than the margin do not contribute
+    let labels = Matrix::from((&device, (2, 1), [0., 0.]));
+    let (loss, dlhs, _) = contrastive(&device, &lhs, &rhs, &labels, 0.1);
+    assert_eq!(loss, 0.);
+    assert_eq!(dlhs.read(), vec![0.; 6]);
+}
+
+#[test]
+fn test_triplet() {
+    let device = CPU::new();
+
+    let anchor = [0., 0., 1., 1., 0.5, -0.5];
+    let negative = [0.2, 0.1, 3., 0., 0.4, -0.4];
+
+    let loss = |anchor: Vec<f64>, positive: Vec<f64>, negative: Vec<f64>| {
+        let anchor = Matrix::from((&device, (2, 3), anchor));
+        let positive = Matrix::from((&device, (2, 3), positive));
+        let negative = Matrix::from((&device, (2, 3), negative));
+        triplet(&device, &anchor, &positive, &negative, 0.5).0
+    };
+
+    let (value, danchor, dpositive, dnegative) = triplet(
+        &device,
+        &Matrix::from((&device, (2, 3), anchor)),
+        &Matrix::from((&device, (2, 3), LHS)),
+        &Matrix::from((&device, (2, 3), negative)),
+        0.5,
+    );
+    assert!(value > 0.);
+
+    assert_approx(
+        &danchor.read(),
+        &numeric_grad(&anchor, |anchor| {
+            loss(anchor, LHS.to_vec(), negative.to_vec())
+        }),
+        1e-6,
+    );
+    assert_approx(
+        &dpositive.read(),
+        &numeric_grad(&LHS, |positive| {
+            loss(anchor.to_vec(), positive, negative.to_vec())
+        }),
+        1e-6,
+    );
+    assert_approx(
+        &dnegative.read(),
+        &numeric_grad(&negative, |negative| {
+            loss(anchor.to_vec(), LHS.to_vec(), negative)
+        }),
+        1e-6,
+    );
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::check_grad;
+use gradients::{
+    BCEWithLogits, BinaryFocal, CategoricalFocal, Loss, Matrix, SoftmaxCrossEntropy, CPU,
+};
+
+#[test]
+fn test_binary_focal_grad() {
+    let logits = [0.5, -1.5, 2., 0., -3., 4.];
+    let targets = [1., 0., 0., 1., 1., 0.];
+
+    check_grad(&BinaryFocal::new(2., Some(0.25)), &logits, &targets, (2, 3));
+    check_grad(&BinaryFocal::new(0.5, None), &logits, &targets, (2, 3));
+}
+
+#[test]
+fn test_categorical_focal_grad() {
+    let logits = [0.3, -1.2, 2., 1.1, 0.4, -0.7];
+    let targets = [0., 0., 1., 1., 0., 0.];
+
+    check_grad(&CategoricalFocal::new(2., None), &logits, &targets, (2, 3));
+    check_grad(
+        &CategoricalFocal::new(1.5, Some(vec![0.25, 0.5, 1.])),
+        &logits,
+        &targets,
+        (2, 3),
+    );
+}
+
+#[test]
+fn test_focal_without_gamma() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), [0.3f32, -1.2, 2., 1.1, 0.4, -0.7]));
+    let targets = Matrix::from((&device, (2, 3), [0., 0., 1., 1., 0., 0.]));
+
+    let focal = CategoricalFocal::new(0., None);
+    let ce = SoftmaxCrossEntropy::default();
+
+    let diff = focal.loss(&device, &logits, &targets).read()[0]
+        - ce.loss(&device, &logits, &targets).read()[0];
+    assert!(diff.abs() < 1e-6);
+
+    let focal = BinaryFocal::new(0., None);
+    let bce = BCEWithLogits::default();
+
+    let diff = focal.loss(&device, &logits, &targets).read()[0]
+        - bce.loss(&device, &logits, &targets).read()[0];
+    assert!(diff.abs() < 1e-6);
+}
+
+#[test]
+fn test_focal_down_weights_easy_examples() {
+    let device = CPU::new();
+
+    let easy = Matrix::from((&device, (1, 1), [4.]));
+    let hard = Matrix::from((&device, (1, 1), [-1.]));
+    let targets = Matrix::from((&device, (1, 1), [1.]));
+
+    let focal = BinaryFocal::new(2., None);
+    let bce = BCEWithLogits::default();
+
+    let ratio = |x: &Matrix<f32>| {
+        focal.loss(&device, x, &targets).read()[0] / bce.loss(&device, x, &targets).read()[0]
+    };
+    assert!(ratio(&easy) < ratio(&hard));
+}
+
+#[test]
+#[should_panic(expected = "number of alpha values")]
+fn test_categorical_focal_alpha_len() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (1, 3), [0.3f32, -1.2, 2.]));
+    let targets = Matrix::from((&device, (1, 3), [0., 0., 1.]));
+
+    CategoricalFocal::new(2., Some(vec![0.25, 0.75])).loss(&device, &logits, &targets);
+}
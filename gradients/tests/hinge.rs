@@ -0,0 +1,82 @@
+use gradients::{prelude::*, GetParam, Hinge, MultiClassHinge, SquaredHinge};
+
+#[test]
+fn test_binary_hinge() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 2), [0.5f32, -2., 3., 0.2]));
+    let targets = Matrix::from((&device, (2, 2), [1., -1., -1., 1.]));
+
+    let hinge = Hinge::default();
+    // (0.5 + 0) / 2 and (4 + 0.8) / 2, averaged
+    let loss = hinge.loss(&device, &preds, &targets).read();
+    assert!((loss[0] - 1.325).abs() < 1e-6);
+    assert_eq!(
+        hinge.grad(&device, &preds, &targets).read(),
+        vec![-0.25, 0., 0.25, -0.25]
+    );
+
+    let squared = SquaredHinge::default();
+    let loss = squared.loss(&device, &preds, &targets).read();
+    assert!((loss[0] - (0.25 + 16. + 0.64) / 4.).abs() < 1e-6);
+
+    let grad = squared.grad(&device, &preds, &targets).read();
+    for (grad, expected) in grad.iter().zip([-0.25, 0., 2., -0.4]) {
+        assert!((grad - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_multi_class_hinge() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), [2., 1.5, -1., 3., 0., 0.5]));
+    let targets = Matrix::from((&device, (2, 3), [1., 0., 0., 0., 0., 1.]));
+
+    let hinge = MultiClassHinge::default();
+    // 1 + 1.5 - 2 and 1 + 3 - 0.5, averaged
+    assert_eq!(hinge.loss(&device, &preds, &targets).read(), vec![2.]);
+    assert_eq!(
+        hinge.grad(&device, &preds, &targets).read(),
+        vec![-0.5, 0.5, 0., 0.5, 0., -0.5]
+    );
+
+    let satisfied = Matrix::from((&device, (1, 3), [3., 1., 0.]));
+    let targets = Matrix::from((&device, (1, 3), [1., 0., 0.]));
+    assert_eq!(hinge.loss(&device, &satisfied, &targets).read(), vec![0.]);
+    assert_eq!(
+        hinge.grad(&device, &satisfied, &targets).read(),
+        vec![0., 0., 0.]
+    );
+}
+
+#[test]
+fn test_linear_svm() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (4, 2), [2., 1., 1., 3., -1., -2., -2., -1.]));
+    let y = Matrix::from((&device, (4, 1), [1., 1., -1., -1.]));
+
+    let mut svm = Linear::<f32, 2, 1>::new(&device, ());
+    let hinge = Hinge::default();
+    let mut sgd = SGD::new(0.1).momentum(0.);
+
+    for _ in 0..100 {
+        let preds = svm.forward(&x);
+        svm.backward(&hinge.grad(&device, &preds, &y));
+        sgd.step(&device, vec![svm.params().unwrap()]);
+    }
+
+    assert_eq!(hinge.loss(&device, &svm.forward(&x), &y).read(), vec![0.]);
+}
+
+#[test]
+#[should_panic(expected = "all zeros")]
+fn test_multi_class_hinge_without_target() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (1, 3), [2f32, 1.5, -1.]));
+    let targets = Matrix::from((&device, (1, 3), [0., 0., 0.]));
+
+    MultiClassHinge::default().loss(&device, &preds, &targets);
+}
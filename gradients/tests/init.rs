@@ -0,0 +1,188 @@
+use gradients::{
+    linear::{
+        FanMode, KaimingNormal, KaimingUniform, LeCunNormal, Nonlinearity, Orthogonal,
+        TruncatedNormal, XavierNormal,
+    },
+    prelude::*,
+};
+
+fn std(values: &[f32]) -> f32 {
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    let var = values.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / values.len() as f32;
+    var.sqrt()
+}
+
+#[test]
+fn test_gain() {
+    assert_eq!(Nonlinearity::Linear.gain(), 1.);
+    assert_eq!(Nonlinearity::ReLU.gain(), 2f64.sqrt());
+    assert_eq!(Nonlinearity::Tanh.gain(), 5. / 3.);
+    assert!((Nonlinearity::LeakyReLU(0.).gain() - 2f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_kaiming_uniform() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 256, 64>::new(&device, KaimingUniform::relu());
+    let bound = (6. / 256f32).sqrt();
+    assert!(lin.weights.read().iter().all(|w| w.abs() <= bound));
+    assert_eq!(lin.bias.unwrap().read(), vec![0.; 64]);
+
+    let fan_out = KaimingUniform::new(FanMode::FanOut, Nonlinearity::Linear);
+    assert_eq!(fan_out.bound(256, 64), (3. / 64f64).sqrt());
+
+    let lin = DynLinear::<f32>::new(&device, 256, 64, fan_out);
+    let bound = (3. / 64f32).sqrt();
+    let weights = lin.weights.read();
+    assert!(weights.iter().all(|w| w.abs() <= bound));
+    assert!(weights.iter().any(|w| w.abs() > (6. / 256f32).sqrt()));
+}
+
+#[test]
+fn test_kaiming_normal() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 512, 256>::new(&device, KaimingNormal::relu());
+    let expected = (2. / 512f32).sqrt();
+    assert!((std(&lin.weights.read()) - expected).abs() < expected * 0.05);
+
+    let init = KaimingNormal::new(FanMode::FanOut, Nonlinearity::Tanh);
+    let lin = Linear::<f32, 512, 256>::new(
+        &device,
+        LinearConfig {
+            init,
+            bias: false,
+            ..Default::default()
+        },
+    );
+    let expected = 5. / 3. / 256f32.sqrt();
+    assert!((std(&lin.weights.read()) - expected).abs() < expected * 0.05);
+    assert!(lin.bias.is_none());
+}
+
+#[network]
+struct ReLUNet {
+    #[init(KaimingNormal::relu())]
+    lin1: Linear<64, 64>,
+    relu1: ReLU,
+    #[init(KaimingUniform::relu())]
+    lin2: Linear<64, 10>,
+}
+
+#[test]
+fn test_kaiming_attribute() {
+    let device = CPU::new();
+    let net = ReLUNet::<f32>::with(&device);
+
+    let bound = (6. / 64f32).sqrt();
+    assert!(net.lin2.weights.read().iter().all(|w| w.abs() <= bound));
+    assert!(std(&net.lin1.weights.read()) > 0.);
+}
+
+#[test]
+fn test_xavier_normal() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 300, 200>::new(&device, XavierNormal::new());
+    let expected = (2. / 500f32).sqrt();
+    assert!((std(&lin.weights.read()) - expected).abs() < expected * 0.05);
+
+    let lin = DynLinear::<f32>::new(&device, 300, 200, XavierNormal::with_gain(2.));
+    assert!((std(&lin.weights.read()) - 2. * expected).abs() < expected * 0.1);
+}
+
+#[test]
+fn test_lecun_normal() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 400, 100>::new(&device, LeCunNormal::new());
+    let expected = (1. / 400f32).sqrt();
+    assert!((std(&lin.weights.read()) - expected).abs() < expected * 0.05);
+    assert_eq!(lin.bias.unwrap().read(), vec![0.; 100]);
+}
+
+#[test]
+fn test_truncated_normal() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 100, 100>::new(&device, TruncatedNormal::with_mean(1., 0.1));
+    let weights = lin.weights.read();
+    assert!(weights.iter().all(|w| (w - 1.).abs() <= 0.2 + 1e-6));
+
+    let mean = weights.iter().sum::<f32>() / weights.len() as f32;
+    assert!((mean - 1.).abs() < 0.01);
+    // truncating at two standard deviations reduces the standard deviation to ~0.88 * std
+    assert!(std(&weights) < 0.1);
+}
+
+#[test]
+#[should_panic(
+    expected = "The standard deviation must be finite and non-negative, but it is -0.1."
+)]
+fn test_truncated_normal_negative_std() {
+    TruncatedNormal::new(-0.1);
+}
+
+#[test]
+#[should_panic(expected = "The standard deviation must be finite and non-negative, but it is NaN.")]
+fn test_truncated_normal_nan_std() {
+    TruncatedNormal::with_mean(1., f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "The standard deviation must be finite and non-negative, but it is -1.")]
+fn test_truncated_normal_invalid_fields() {
+    let device = CPU::new();
+    Linear::<f32, 4, 8>::new(&device, Box::new(TruncatedNormal { mean: 0., std: -1. }));
+}
+
+/// Returns `W^T W` for a row-major `rows x cols` matrix.
+fn gram(weights: &[f32], rows: usize, cols: usize) -> Vec<f32> {
+    let mut gram = vec![0.; cols * cols];
+    for a in 0..cols {
+        for b in 0..cols {
+            gram[a * cols + b] = (0..rows)
+                .map(|row| weights[row * cols + a] * weights[row * cols + b])
+                .sum();
+        }
+    }
+    gram
+}
+
+fn assert_identity(gram: &[f32], size: usize, scale: f32) {
+    for a in 0..size {
+        for b in 0..size {
+            let expected = if a == b { scale } else { 0. };
+            assert!((gram[a * size + b] - expected).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn test_orthogonal_columns() {
+    let device = CPU::new();
+
+    let lin = Linear::<f32, 32, 16>::new(&device, Orthogonal::new());
+    assert_identity(&gram(&lin.weights.read(), 32, 16), 16, 1.);
+
+    let lin = Linear::<f32, 24, 24>::new(&device, Orthogonal::with_gain(2.));
+    assert_identity(&gram(&lin.weights.read(), 24, 24), 24, 4.);
+}
+
+#[test]
+fn test_orthogonal_rows() {
+    let device = CPU::new();
+
+    let lin = DynLinear::<f32>::new(&device, 8, 20, Orthogonal::new());
+    let weights = lin.weights.read();
+
+    // the rows of the weights are the columns of the transposed weights
+    let mut transposed = vec![0.; weights.len()];
+    for row in 0..8 {
+        for col in 0..20 {
+            transposed[col * 8 + row] = weights[row * 20 + col];
+        }
+    }
+    assert_identity(&gram(&transposed, 20, 8), 8, 1.);
+}
@@ -0,0 +1,40 @@
+use gradients::{nll, nll_grad, LogSoftmax, Matrix, CPU};
+
+#[test]
+fn test_log_softmax_large_logits() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 3), [1000., 1001., 1002., -5., 0., 5.]));
+
+    let mut log_softmax = LogSoftmax::<f32>::new();
+    let out = log_softmax.forward(&x);
+
+    for value in out.read() {
+        assert!(value.is_finite());
+    }
+
+    let row_sum = out.read()[..3].iter().map(|x| x.exp()).sum::<f32>();
+    assert!((row_sum - 1.).abs() < 1e-3);
+}
+
+#[test]
+fn test_log_softmax_nll() {
+    let device = CPU::new();
+
+    let x = Matrix::from((&device, (2, 3), [1., 2., 3., 3., 2., 1.]));
+    let y = Matrix::from((&device, (2, 3), [0., 0., 1., 1., 0., 0.]));
+
+    let mut log_softmax = LogSoftmax::<f32>::new();
+    let out = log_softmax.forward(&x);
+
+    let loss = nll(&out, &y);
+    assert!((loss - 0.407606).abs() < 1e-5);
+
+    let grad = log_softmax.backward(&nll_grad(&out, &y));
+
+    // softmax - targets, averaged over the samples
+    let expected = [0.045015, 0.122364, -0.167379, -0.167379, 0.122364, 0.045015];
+    for (grad, expected) in grad.read().iter().zip(expected) {
+        assert!((grad - expected).abs() < 1e-5);
+    }
+}
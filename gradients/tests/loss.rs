@@ -0,0 +1,121 @@
+mod common;
+
+use common::assert_approx;
+use gradients::{prelude::*, NLL};
+
+#[test]
+fn test_mse_matches_nn() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), [0.1, 0.4, 0.2, 0.9, 0.3, 0.5]));
+    let targets = Matrix::from((&device, (2, 3), [0., 1., 0., 1., 0., 0.]));
+
+    let loss = MSE::default().loss(&device, &preds, &targets);
+    assert_approx(&loss.read(), &[mse(&preds, &targets)], 1e-5);
+
+    let grad = MSE::default().grad(&device, &preds, &targets);
+    assert_approx(&grad.read(), &mse_grad(&preds, &targets).read(), 1e-5);
+}
+
+#[test]
+fn test_cce_matches_nn() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), [0.1, 0.7, 0.2, 0.6, 0.3, 0.1]));
+    let targets = Matrix::from((&device, (2, 3), [0., 1., 0., 1., 0., 0.]));
+
+    let loss = CCE::default().loss(&device, &preds, &targets);
+    assert_approx(&loss.read(), &[cce(&device, &preds, &targets)], 1e-5);
+
+    let grad = CCE::default().grad(&device, &preds, &targets);
+    assert_approx(
+        &grad.read(),
+        &cce_grad(&device, &preds, &targets).read(),
+        1e-5,
+    );
+}
+
+#[test]
+fn test_reduction_and_weights() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 2), [1., 2., 3., 4.]));
+    let targets = Matrix::from((&device, (2, 2), [1., 1., 2., 2.]));
+
+    let none = MSE {
+        reduction: Reduction::None,
+        ..Default::default()
+    };
+    assert_eq!(none.loss(&device, &preds, &targets).read(), vec![0.5, 2.5]);
+    assert_eq!(
+        none.grad(&device, &preds, &targets).read(),
+        vec![0., 1., 1., 2.]
+    );
+
+    let weighted = MSE {
+        reduction: Reduction::Sum,
+        weights: Some(vec![2., 0.]),
+    };
+    assert_eq!(weighted.loss(&device, &preds, &targets).read(), vec![1.]);
+    assert_eq!(
+        weighted.grad(&device, &preds, &targets).read(),
+        vec![0., 2., 0., 0.]
+    );
+
+    let mean = MSE {
+        weights: Some(vec![2., 0.]),
+        ..Default::default()
+    };
+    assert_eq!(mean.loss(&device, &preds, &targets).read(), vec![0.5]);
+}
+
+#[test]
+fn test_nll_loss_trait() {
+    let device = CPU::new();
+
+    let log_probs = Matrix::from((&device, (1, 2), [-0.5f32, -2.]));
+    let targets = Matrix::from((&device, (1, 2), [0., 1.]));
+
+    let loss = NLL::default().loss(&device, &log_probs, &targets);
+    assert_eq!(loss.read(), vec![2.]);
+}
+
+fn train_step<'a, L: Loss<f32>>(
+    device: &'a CPU,
+    net: &mut impl gradients::NeuralNetwork<'a, f32>,
+    loss: &L,
+    x: &Matrix<'a, f32>,
+    y: &Matrix<'a, f32>,
+) -> f32 {
+    let preds = net.forward(x);
+    let grad = loss.grad(device, &preds, y);
+    net.backward(&grad);
+    loss.loss(device, &preds, y).read()[0]
+}
+
+#[network]
+struct Net {
+    lin1: Linear<2, 4>,
+    tanh1: Tanh,
+    lin2: Linear<4, 1>,
+}
+
+#[test]
+fn test_generic_over_loss() {
+    let device = CPU::new();
+    let mut net = Net::with(&device);
+    let mut opt = Adam::new(0.01);
+
+    let x = Matrix::from((&device, (4, 2), [0., 0., 0., 1., 1., 0., 1., 1.]));
+    let y = Matrix::from((&device, (4, 1), [0., 1., 1., 0.]));
+
+    let first = train_step(&device, &mut net, &MSE::default(), &x, &y);
+    opt.step(&device, net.params());
+
+    let mut last = first;
+    for _ in 0..200 {
+        last = train_step(&device, &mut net, &MSE::default(), &x, &y);
+        opt.step(&device, net.params());
+    }
+    assert!(last < first);
+}
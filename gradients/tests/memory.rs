@@ -0,0 +1,72 @@
+use gradients::{prelude::*, MemoryFootprint, NeuralNetwork};
+
+#[network]
+struct Net {
+    lin1: Linear<4, 16>,
+    relu1: ReLU,
+    lin2: Linear<16, 2>,
+    softmax: Softmax,
+}
+
+const PARAMS: usize = 4 * 16 + 16 + 16 * 2 + 2;
+
+#[test]
+fn test_memory_footprint_adam_vs_sgd() {
+    let device = CPU::new();
+    let net = Net::<f32>::with(&device);
+
+    let adam = net.memory_footprint(&Adam::new(0.001), 32);
+    assert_eq!(adam.params, PARAMS);
+    assert_eq!(adam.param_bytes, PARAMS * 4);
+    assert_eq!(adam.gradient_bytes, PARAMS * 4);
+    assert_eq!(adam.optimizer_bytes, 2 * PARAMS * 4);
+
+    let sgd = net.memory_footprint(&SGD::new(0.1), 32);
+    assert_eq!(sgd.optimizer_bytes, PARAMS * 4);
+
+    let sgd = net.memory_footprint(&SGD::new(0.1).momentum(0.), 32);
+    assert_eq!(sgd.optimizer_bytes, 0);
+
+    assert_eq!(adam.activation_bytes, sgd.activation_bytes);
+    assert_eq!(adam.total_bytes() - sgd.total_bytes(), adam.optimizer_bytes);
+}
+
+#[test]
+fn test_activation_memory() {
+    let device = CPU::new();
+    let net = Net::<f32>::with(&device);
+
+    // lin1, relu1, lin2 and softmax keep 4, 16, 16 and 2 features per sample
+    let footprint = net.memory_footprint(&Adam::new(0.001), 64);
+    assert_eq!(footprint.activation_bytes, (4 + 16 + 16 + 2) * 64 * 4);
+
+    let doubled = MemoryFootprint::new::<f32>(&net.layer_shapes(), &Adam::<f32>::new(0.001), 128);
+    assert_eq!(doubled.activation_bytes, 2 * footprint.activation_bytes);
+
+    let f64_net = Net::<f64>::with(&device);
+    let f64_footprint = f64_net.memory_footprint(&Adam::new(0.001), 64);
+    assert_eq!(f64_footprint.total_bytes(), 2 * footprint.total_bytes());
+}
+
+#[test]
+fn test_sequential_memory() {
+    let device = CPU::new();
+
+    let net = Sequential::new()
+        .add(Linear::<f32, 2, 8>::new(&device, ()))
+        .add(Tanh::new())
+        .add(Linear::<f32, 8, 1>::new(&device, ()));
+
+    let footprint = net.memory_footprint(&SGD::new(0.1), 5);
+    assert_eq!(footprint.params, 2 * 8 + 8 + 8 + 1);
+    assert_eq!(footprint.activation_bytes, (2 + 8 + 8) * 5 * 4);
+
+    let shapes = net.layer_shapes();
+    assert_eq!(
+        shapes
+            .iter()
+            .map(|layer| layer.param_count())
+            .sum::<usize>(),
+        footprint.params
+    );
+}
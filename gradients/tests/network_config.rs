@@ -0,0 +1,125 @@
+use gradients::{number::Float, prelude::*, Alloc, Conv2D, GraphReturn, LayerShape, NeuralNetwork};
+
+/// Initializes every weight with the same value.
+struct Constant(f64);
+
+impl<'a, T: Float, D: Alloc<T> + GraphReturn> DynInit<'a, T, D> for Constant {
+    fn init_dyn(
+        &self,
+        device: &'a D,
+        inputs: usize,
+        outputs: usize,
+        with_bias: bool,
+    ) -> (Matrix<'a, T>, Option<Matrix<'a, T>>) {
+        let weights = Matrix::from((
+            device,
+            (inputs, outputs),
+            vec![T::as_generic(self.0); inputs * outputs],
+        ));
+        let bias = with_bias.then(|| Matrix::from((device, 1, outputs)));
+        (weights, bias)
+    }
+}
+
+#[network]
+struct ConfiguredNet {
+    #[init(Constant(0.5))]
+    #[bias(false)]
+    lin1: Linear<4, 8>,
+    relu: ReLU,
+    #[l2(1e-3)]
+    lin2: Linear<8, 2>,
+}
+
+#[test]
+fn test_linear_attributes() {
+    let device = CPU::new();
+    let net = ConfiguredNet::<f32>::with(&device);
+
+    assert!(net.lin1.bias.is_none());
+    assert_eq!(net.lin1.weights.read(), vec![0.5; 32]);
+    assert_eq!(net.lin1.l2_reg, 0.);
+
+    assert!(net.lin2.bias.is_some());
+    assert!((net.lin2.l2_reg - 1e-3).abs() < 1e-9);
+}
+
+#[test]
+fn test_train_without_bias() {
+    let device = CPU::new();
+    let mut net = ConfiguredNet::<f32>::with(&device);
+
+    let xs = Matrix::from((&device, (2, 4), [0.5, -0.2, 0.1, 0.9, -0.3, 0.7, 0.2, -0.6]));
+    let ys = Matrix::from((&device, (2, 2), [1., 0., 0., 1.]));
+
+    let mut sgd = SGD::new(0.1);
+    let mut adam = Adam::new(1e-3);
+
+    for _ in range(10) {
+        let preds = net.forward(&xs);
+        net.backward(&mse_grad(&preds, &ys));
+        sgd.step(&device, net.params());
+        adam.step(&device, net.params());
+    }
+    assert!(net.lin1.bias.is_none());
+}
+
+#[network]
+struct ConvNet {
+    #[conv(input = (28, 28), kernel = (3, 3), blocks = 5)]
+    conv: Conv2D,
+    lin: Linear<{ 5 * 26 * 26 }, 10>,
+    softmax: Softmax,
+}
+
+#[test]
+fn test_conv_attribute() {
+    let device = CPU::new();
+    let net = ConvNet::<f32>::with(&device);
+
+    assert_eq!(net.conv.kernel_shape, (3, 3));
+    assert_eq!(net.conv.output_size(None), Some(5 * 26 * 26));
+}
+
+#[network]
+struct MismatchedConvNet {
+    #[conv(input = (28, 28), kernel = (3, 3), blocks = 4)]
+    conv: Conv2D,
+    lin: Linear<{ 5 * 26 * 26 }, 10>,
+}
+
+#[test]
+#[should_panic(
+    expected = "The output size of \"conv\" (2704) does not match the input size of \"lin\" (3380)."
+)]
+fn test_conv_attribute_mismatch() {
+    let device = CPU::new();
+    MismatchedConvNet::<f32>::with(&device);
+}
+
+mod own_with_device {
+    use gradients::prelude::*;
+
+    /// Shadows `gradients::WithDevice`, which the generated code must not pick up.
+    #[allow(dead_code)]
+    trait WithDevice {
+        fn with(device: &CPU) -> Self;
+    }
+
+    #[network]
+    pub struct Net {
+        #[init(Glorot)]
+        lin1: Linear<4, 8>,
+        relu: ReLU,
+        lin2: Linear<8, 2>,
+    }
+}
+
+#[test]
+fn test_network_with_shadowed_trait() {
+    let device = CPU::new();
+    let mut net: own_with_device::Net<f32> = gradients::WithDevice::with(&device);
+
+    let xs = Matrix::from((&device, (1, 4), [0.5, -0.2, 0.1, 0.9]));
+    assert_eq!(net.forward(&xs).dims(), (1, 2));
+}
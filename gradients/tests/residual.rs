@@ -0,0 +1,63 @@
+use gradients::{prelude::*, NeuralNetwork};
+
+#[derive(NeuralNetwork)]
+struct ResBlock<'a, T> {
+    lin1: Linear<'a, T, 4, 4>,
+    relu1: ReLU<'a, T>,
+    #[residual(from = "lin1")]
+    lin2: Linear<'a, T, 4, 4>,
+}
+
+#[test]
+fn test_residual_forward_backward() {
+    let device = CPU::new();
+
+    let mut net: ResBlock<f32> = ResBlock {
+        lin1: Linear::new(&device, ()),
+        lin2: Linear::new(&device, ()),
+        ..Default::default()
+    };
+
+    let x = Matrix::from((&device, (2, 4), [0.5, -0.2, 0.1, 0.9, -0.3, 0.7, 0.2, -0.6]));
+    let grad = Matrix::from((&device, (2, 4), [0.1, 0.2, -0.3, 0.4, 0.5, -0.1, 0.2, 0.3]));
+
+    let lin1_out = net.lin1.forward(&x);
+    let relu1_out = net.relu1.forward(&lin1_out);
+    let expected = net.lin2.forward(&relu1_out) + &lin1_out;
+
+    let dlin2 = net.lin2.backward(&grad);
+    let dlin1_out = net.relu1.backward(&dlin2) + &grad;
+    let expected_grad = net.lin1.backward(&dlin1_out);
+
+    assert_eq!(net.forward(&x).read(), expected.read());
+    assert_eq!(net.backward(&grad).read(), expected_grad.read());
+}
+
+#[network]
+struct InputSkip {
+    lin1: Linear<4, 4>,
+    tanh1: Tanh,
+    #[residual(from = "inputs")]
+    lin2: Linear<4, 4>,
+}
+
+#[test]
+fn test_residual_from_inputs() {
+    let device = CPU::new();
+
+    let mut net = InputSkip::<f32>::with(&device);
+
+    let x = Matrix::from((&device, (1, 4), [0.5, -0.2, 0.1, 0.9]));
+    let grad = Matrix::from((&device, (1, 4), [0.1, 0.2, -0.3, 0.4]));
+
+    let lin1_out = net.lin1.forward(&x);
+    let tanh1_out = net.tanh1.forward(&lin1_out);
+    let expected = net.lin2.forward(&tanh1_out) + &x;
+
+    let dlin2 = net.lin2.backward(&grad);
+    let dtanh1 = net.tanh1.backward(&dlin2);
+    let expected_grad = net.lin1.backward(&dtanh1) + &grad;
+
+    assert_eq!(net.forward(&x).read(), expected.read());
+    assert_eq!(net.backward(&grad).read(), expected_grad.read());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::check_grad;
+use gradients::{prelude::*, Huber, LogCosh, Quantile, MAE};
+
+#[test]
+fn test_robust_loss_grads() {
+    let preds = [0.5, -1.5, 2., 0.1, -3., 4.];
+    let targets = [1., 0., 0., 1., 1., 0.5];
+
+    check_grad(&MAE::default(), &preds, &targets, (2, 3));
+    check_grad(&Huber::new(1.), &preds, &targets, (2, 3));
+    check_grad(&Huber::new(0.3), &preds, &targets, (2, 3));
+    check_grad(&LogCosh::default(), &preds, &targets, (2, 3));
+    check_grad(&Quantile::new(0.9), &preds, &targets, (2, 3));
+}
+
+#[test]
+fn test_robust_loss_values() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (1, 2), [3f32, -1.]));
+    let targets = Matrix::from((&device, (1, 2), [1., -1.5]));
+
+    assert_eq!(
+        MAE::default().loss(&device, &preds, &targets).read(),
+        vec![1.25]
+    );
+
+    // 1 * (2 - 0.5) and 0.5 * 0.5^2
+    assert_eq!(
+        Huber::new(1.).loss(&device, &preds, &targets).read(),
+        vec![0.8125]
+    );
+
+    // (0.1 - 1) * -2 and (0.1 - 1) * -0.5, averaged
+    let quantile = Quantile::new(0.1).loss(&device, &preds, &targets).read();
+    assert!((quantile[0] - 1.125).abs() < 1e-6);
+
+    let large = Matrix::from((&device, (1, 1), [1000f32]));
+    let zero = Matrix::from((&device, (1, 1), [0.]));
+    let log_cosh = LogCosh::default().loss(&device, &large, &zero).read();
+    assert!((log_cosh[0] - (1000. - 2f32.ln())).abs() < 1e-3);
+}
+
+#[network]
+struct Net {
+    lin1: Linear<1, 16>,
+    relu1: ReLU,
+    lin2: Linear<16, 1>,
+}
+
+#[test]
+fn test_huber_drives_backward() {
+    let device = CPU::new();
+    let mut net = Net::with(&device);
+
+    let (x, y) = gradients::create_line::<f32, _>(&device, 0, 100);
+    let huber = Huber::new(0.5);
+    let mut opt = Adam::new(1e-2);
+
+    let first = huber.loss(&device, &net.forward(&x), &y).read()[0];
+
+    for _ in 0..100 {
+        let preds = net.forward(&x);
+        net.backward(&huber.grad(&device, &preds, &y));
+        opt.step(&device, net.params());
+    }
+
+    let last = huber.loss(&device, &net.forward(&x), &y).read()[0];
+    assert!(last < first);
+}
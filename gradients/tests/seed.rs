@@ -0,0 +1,70 @@
+use gradients::{prelude::*, seed, Conv2D, NeuralNetwork};
+
+#[network]
+struct Xor {
+    lin1: Linear<2, 16>,
+    tanh1: Tanh,
+    lin2: Linear<16, 2>,
+}
+
+fn train(device: &CPU, seed_value: u64) -> Vec<f32> {
+    seed(seed_value);
+    let mut net = Xor::<f32>::with(device);
+
+    let xs = Matrix::from((device, 4, 2, [0., 0., 0., 1., 1., 0., 1., 1.]));
+    let ys = Matrix::from((device, 4, 2, [1., 0., 0., 1., 0., 1., 1., 0.]));
+
+    let mut sgd = SGD::new(0.1);
+
+    let mut losses = Vec::new();
+    for _ in range(50) {
+        let preds = net.forward(&xs);
+        losses.push(mse(&preds, &ys));
+
+        net.backward(&mse_grad(&preds, &ys));
+        sgd.step(device, net.params());
+    }
+    losses
+}
+
+#[test]
+fn test_same_seed_same_losses() {
+    let device = CPU::new();
+
+    assert_eq!(train(&device, 7), train(&device, 7));
+    assert_ne!(train(&device, 7), train(&device, 8));
+}
+
+#[test]
+fn test_seeded_inits() {
+    let device = CPU::new();
+
+    seed(1);
+    let glorot = Linear::<f32, 8, 4>::new(&device, ());
+    let uniform = Linear::<f32, 8, 4>::new(&device, RandomUniform::new(-0.5, 0.5));
+
+    seed(1);
+    assert_eq!(
+        glorot.weights.read(),
+        Linear::<f32, 8, 4>::new(&device, ()).weights.read()
+    );
+    assert_eq!(
+        uniform.weights.read(),
+        Linear::<f32, 8, 4>::new(&device, RandomUniform::new(-0.5, 0.5))
+            .weights
+            .read()
+    );
+}
+
+#[test]
+fn test_seeded_conv() {
+    let device = CPU::new();
+    let x = Matrix::from((&device, (1, 16), [0.5; 16]));
+
+    seed(3);
+    let mut conv1 = Conv2D::<f32>::new(&device, (4, 4), (3, 3), 2);
+    seed(3);
+    let mut conv2 = Conv2D::<f32>::new(&device, (4, 4), (3, 3), 2);
+
+    assert_eq!(conv1.forward(&x).read(), conv2.forward(&x).read());
+}
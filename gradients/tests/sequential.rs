@@ -0,0 +1,66 @@
+use gradients::{prelude::*, NeuralNetwork};
+
+fn activation<'a>(name: &str) -> Box<dyn Layer<'a, f32> + 'a> {
+    match name {
+        "relu" => Box::new(ReLU::new()),
+        "tanh" => Box::new(Tanh::new()),
+        _ => panic!("unknown activation {name}"),
+    }
+}
+
+#[test]
+fn test_sequential_from_config() {
+    let device = CPU::new();
+
+    let mut net = Sequential::new();
+    net.push(Box::new(Linear::<f32, 2, 16>::new(&device, ())));
+    for name in ["tanh", "relu"] {
+        net.push(activation(name));
+    }
+    net.push(Box::new(Linear::<f32, 16, 2>::new(&device, ())));
+
+    assert_eq!(net.len(), 4);
+
+    let xs = Matrix::from((&device, 4, 2, [0., 0., 0., 1., 1., 0., 1., 1.]));
+    let ys = Matrix::from((&device, 4, 2, [1., 0., 0., 1., 0., 1., 1., 0.]));
+
+    let mut sgd = SGD::new(0.1);
+
+    let mut first_loss = None;
+    let mut loss = 0.;
+
+    for _ in range(300) {
+        let preds = net.forward(&xs);
+        loss = mse(&preds, &ys);
+        first_loss.get_or_insert(loss);
+
+        net.backward(&mse_grad(&preds, &ys));
+        sgd.step(&device, net.params());
+    }
+
+    assert_eq!(net.params().len(), 2);
+    assert!(loss < first_loss.unwrap());
+}
+
+#[test]
+fn test_sequential_matches_layers() {
+    let device = CPU::new();
+
+    let mut lin = Linear::<f32, 2, 3>::new(&device, ());
+    let mut relu = ReLU::new();
+
+    let mut copy = Linear::<f32, 2, 3>::new(&device, ());
+    copy.weights = lin.weights.shallow_or_clone();
+    copy.bias = lin.bias.as_ref().map(|bias| bias.shallow_or_clone());
+
+    let mut net = Sequential::new().add(copy).add(ReLU::new());
+
+    let x = Matrix::from((&device, (2, 2), [0.5, -1., 2., 0.25]));
+    let grad = Matrix::from((&device, (2, 3), [1., -1., 0.5, 0.25, 2., -3.]));
+
+    let expected = relu.forward(&lin.forward(&x));
+    assert_eq!(net.forward(&x).read(), expected.read());
+
+    let expected = lin.backward(&relu.backward(&grad));
+    assert_eq!(net.backward(&grad).read(), expected.read());
+}
@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use gradients::{
+    check_shapes, number::Float, prelude::*, CDatatype, Conv2D, GetParam, LayerShape,
+    NeuralNetwork, WithDevice,
+};
+
+#[network]
+struct ComputedSizes {
+    lin1: Linear<{ 28 * 28 }, { 2 * 64 }>,
+    relu1: ReLU,
+    lin2: Linear<128, 10>,
+    softmax: Softmax,
+}
+
+#[test]
+fn test_computed_sizes() {
+    let device = CPU::new();
+    let _net = ComputedSizes::<f32>::with(&device);
+}
+
+#[test]
+fn test_conv_shape() {
+    let device = CPU::new();
+
+    let conv = Conv2D::<f32>::new(&device, (28, 28), (3, 3), 5);
+    assert_eq!(conv.input_size(), Some(28 * 28));
+    assert_eq!(conv.output_size(None), Some(5 * 26 * 26));
+
+    let lin = Linear::<f32, { 5 * 26 * 26 }, 10>::new(&device, ());
+    let relu = ReLU::<f32>::new();
+    check_shapes(&[("conv", &conv), ("relu", &relu), ("lin", &lin)]);
+}
+
+#[test]
+#[should_panic(
+    expected = "The output size of \"conv\" (3380) does not match the input size of \"lin\" (676)."
+)]
+fn test_conv_shape_mismatch() {
+    let device = CPU::new();
+
+    let conv = Conv2D::<f32>::new(&device, (28, 28), (3, 3), 5);
+    let lin = Linear::<f32, { 26 * 26 }, 10>::new(&device, ());
+    check_shapes(&[("conv", &conv), ("lin", &lin)]);
+}
+
+#[test]
+fn test_unconfigured_conv_is_not_checked() {
+    let conv = Conv2D::<f32>::default();
+    assert_eq!(conv.input_size(), None);
+    assert_eq!(conv.output_size(Some(10)), None);
+}
+
+/// A layer, which implements the traits needed by `#[network]`, but not `LayerShape`.
+struct Double<'a, T> {
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Default for Double<'a, T> {
+    fn default() -> Self {
+        Double {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> GetParam<'a, T> for Double<'a, T> {}
+impl<'a, T> WithDevice<'a, T> for Double<'a, T> {}
+
+impl<'a, T: Float + CDatatype> Double<'a, T> {
+    fn forward(&mut self, inputs: &Matrix<'a, T>) -> Matrix<'a, T> {
+        inputs + inputs
+    }
+
+    fn backward(&mut self, grad: &Matrix<'a, T>) -> Matrix<'a, T> {
+        grad + grad
+    }
+}
+
+#[network]
+struct CustomLayerNet {
+    lin1: Linear<4, 8>,
+    double: Double,
+    lin2: Linear<8, 2>,
+}
+
+#[test]
+fn test_layer_without_shape() {
+    let device = CPU::new();
+    let mut net = CustomLayerNet::<f32>::with(&device);
+
+    let out = net.forward(&Matrix::from((&device, (3, 4), [0.5; 12])));
+    assert_eq!(out.dims(), (3, 2));
+
+    let summary = net.summary_table();
+    assert_eq!(summary.layers[1].input_size, Some(8));
+    assert_eq!(summary.layers[1].output_size, None);
+    assert_eq!(summary.total_params(), 4 * 8 + 8 + 8 * 2 + 2);
+
+    let footprint = net.memory_footprint(&SGD::new(0.1), 1);
+    assert_eq!(footprint.params, summary.total_params());
+    assert_eq!(footprint.activation_bytes, (4 + 8 + 8) * 4);
+}
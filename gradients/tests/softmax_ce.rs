@@ -0,0 +1,29 @@
+use gradients::{softmax_cross_entropy, Matrix, CPU};
+
+#[test]
+fn test_softmax_cross_entropy() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), [1., 2., 3., 3., 2., 1.]));
+    let targets = Matrix::from((&device, (2, 3), [0., 0., 1., 1., 0., 0.]));
+
+    let (loss, grad) = softmax_cross_entropy(&logits, &targets);
+    assert!((loss - 0.407606f32).abs() < 1e-5);
+
+    let expected = [0.045015, 0.122364, -0.167379, -0.167379, 0.122364, 0.045015];
+    for (grad, expected) in grad.read().iter().zip(expected) {
+        assert!((grad - expected).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_softmax_cross_entropy_large_logits() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (1, 3), [1000., 0., -1000.]));
+    let targets = Matrix::from((&device, (1, 3), [0., 1., 0.]));
+
+    let (loss, grad) = softmax_cross_entropy(&logits, &targets);
+    assert!((loss - 1000f32).abs() < 1e-3);
+    assert_eq!(grad.read(), vec![1., -1., 0.]);
+}
@@ -0,0 +1,108 @@
+use gradients::{
+    nn::{cce, cce_grad},
+    sparse_cce, sparse_cce_grad, Loss, Matrix, OneHotMat, SoftmaxCrossEntropy,
+    SparseSoftmaxCrossEntropy, CPU,
+};
+
+#[test]
+fn test_sparse_cce_matches_cce() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((
+        &device,
+        (3, 3),
+        [0.1f32, 0.7, 0.2, 0.6, 0.3, 0.1, 0.2, 0.2, 0.6],
+    ));
+    let classes = Matrix::from((&device, (3, 1), [1., 0., 2.]));
+    let onehot = classes.onehot();
+
+    let loss = sparse_cce(&preds, &classes);
+    assert!((loss - cce(&device, &preds, &onehot)).abs() < 1e-6);
+
+    let grad = sparse_cce_grad(&preds, &classes);
+    for (grad, expected) in grad
+        .read()
+        .iter()
+        .zip(cce_grad(&device, &preds, &onehot).read())
+    {
+        assert!((grad - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_sparse_softmax_cross_entropy() {
+    let device = CPU::new();
+
+    let logits = Matrix::from((&device, (2, 3), [1f32, 2., 3., 3., 2., 1.]));
+    let classes = Matrix::from((&device, (2, 1), [2., 0.]));
+    let onehot = classes.onehot();
+
+    let sparse = SparseSoftmaxCrossEntropy::default();
+    let dense = SoftmaxCrossEntropy::default();
+
+    let loss = sparse.loss(&device, &logits, &classes).read();
+    assert!((loss[0] - dense.loss(&device, &logits, &onehot).read()[0]).abs() < 1e-6);
+
+    let grad = sparse.grad(&device, &logits, &classes).read();
+    for (grad, expected) in grad
+        .iter()
+        .zip(dense.grad(&device, &logits, &onehot).read())
+    {
+        assert!((grad - expected).abs() < 1e-6);
+    }
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_sparse_cce_grad_cl() -> custos::Result<()> {
+    let device = custos::CLDevice::new(0)?;
+
+    let preds = Matrix::from((&device, (2, 3), [0.1f32, 0.7, 0.2, 0.6, 0.3, 0.1]));
+    let classes = Matrix::from((&device, (2, 1), [1., 0.]));
+
+    let grad = sparse_cce_grad(&preds, &classes).read();
+    let expected = [0., -1. / 1.4, 0., -1. / 1.2, 0., 0.];
+
+    for (grad, expected) in grad.iter().zip(expected) {
+        assert!((grad - expected).abs() < 1e-5);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_sparse_cce_invalid_class_cl() -> custos::Result<()> {
+    let device = custos::CLDevice::new(0)?;
+
+    let preds = Matrix::from((&device, (2, 3), [0.1f32, 0.7, 0.2, 0.6, 0.3, 0.1]));
+    let classes = Matrix::from((&device, (2, 1), [3., 0.]));
+
+    assert!(sparse_cce(&preds, &classes).is_nan());
+
+    let grad = sparse_cce_grad(&preds, &classes).read();
+    assert!(grad[..3].iter().all(|grad| grad.is_nan()));
+    assert!(!grad[3..].iter().any(|grad| grad.is_nan()));
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_sparse_cce_invalid_class_loss() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), [0.1f32, 0.7, 0.2, 0.6, 0.3, 0.1]));
+    let classes = Matrix::from((&device, (2, 1), [0., -1.]));
+
+    sparse_cce(&preds, &classes);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_sparse_cce_invalid_class() {
+    let device = CPU::new();
+
+    let preds = Matrix::from((&device, (2, 3), [0.1f32, 0.7, 0.2, 0.6, 0.3, 0.1]));
+    let classes = Matrix::from((&device, (2, 1), [3., 0.]));
+
+    sparse_cce_grad(&preds, &classes);
+}
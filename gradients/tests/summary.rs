@@ -0,0 +1,62 @@
+use gradients::{prelude::*, Conv2D, LayerSummary, NeuralNetwork};
+
+#[network]
+struct Mnist {
+    #[conv(input = (28, 28), kernel = (3, 3), blocks = 2)]
+    conv: Conv2D,
+    #[bias(false)]
+    lin1: Linear<{ 2 * 26 * 26 }, 64>,
+    relu1: ReLU,
+    lin2: Linear<64, 10>,
+    softmax: Softmax,
+}
+
+#[test]
+fn test_summary_table() {
+    let device = CPU::new();
+    let net = Mnist::<f32>::with(&device);
+
+    let summary = net.summary_table();
+
+    assert_eq!(
+        summary.layers[1],
+        LayerSummary {
+            name: "lin1",
+            layer: "Linear<{ 2 * 26 * 26 }, 64>",
+            input_size: Some(1352),
+            output_size: Some(64),
+            params: 1352 * 64,
+        }
+    );
+    assert_eq!(summary.layers[4].layer, "Softmax");
+    assert_eq!(summary.layers[4].input_size, Some(10));
+
+    let conv_params = 2 * (3 * 3 + 26 * 26);
+    assert_eq!(
+        summary.total_params(),
+        conv_params + 1352 * 64 + 64 * 10 + 10
+    );
+
+    let table = summary.to_string();
+    assert!(table.starts_with("Field"));
+    assert!(table.contains("(None, 784)"));
+    assert!(table.ends_with(&format!("Total params: {}", summary.total_params())));
+
+    net.summary();
+}
+
+#[network]
+struct Unknown {
+    relu: ReLU,
+    lin: Linear<8, 4>,
+}
+
+#[test]
+fn test_summary_unknown_sizes() {
+    let device = CPU::new();
+    let net = Unknown::<f32>::with(&device);
+
+    let summary = net.summary_table();
+    assert_eq!(summary.layers[0].input_size, None);
+    assert!(summary.to_string().contains("(None, ?)"));
+}